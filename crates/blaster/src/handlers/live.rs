@@ -8,7 +8,7 @@ use axum::{
     http::StatusCode,
     response::IntoResponse,
 };
-use blastview::view::View;
+use blastview::view::RenderableView;
 use futures::{SinkExt, StreamExt};
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
@@ -22,7 +22,7 @@ pub async fn live_handler<V, F>(
     State(state): State<Arc<AppState<V, F>>>,
 ) -> impl IntoResponse
 where
-    V: View + Send + Sync + 'static,
+    V: RenderableView + Send + Sync + 'static,
     F: Fn() -> V + Send + Sync + 'static,
 {
     let Some(session) = Uuid::parse_str(&session_id)
@@ -35,13 +35,12 @@ where
         .into_response()
 }
 
-async fn handle_ws<V, F>(socket: WebSocket, session: (LiveSession, flume::Receiver<Patch>))
+async fn handle_ws<V, F>(socket: WebSocket, session: (Arc<LiveSession>, flume::Receiver<Patch>))
 where
-    V: View + Send + Sync + 'static,
+    V: RenderableView + Send + Sync + 'static,
     F: Fn() -> V + Send + Sync,
 {
     let (session, patch_rx) = session;
-    let session = Arc::new(session);
     Arc::clone(&session).begin_re_render_task();
 
     let (mut sender, mut receiver) = socket.split();
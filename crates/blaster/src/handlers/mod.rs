@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use axum::{Router, routing::get};
-use blastview::view::View;
+use blastview::view::RenderableView;
 
 use crate::state::AppState;
 
@@ -10,7 +10,7 @@ pub mod ssr;
 
 pub fn router<V, F>() -> Router<Arc<AppState<V, F>>>
 where
-    V: View + Send + Sync + 'static,
+    V: RenderableView + Send + Sync + 'static,
     F: Fn() -> V + Send + Sync + 'static,
 {
     Router::new()
@@ -1,47 +1,113 @@
-use std::sync::Arc;
+use std::{convert::Infallible, future::ready, sync::Arc};
 
 use axum::{
+    body::Body,
     extract::State,
-    response::{Html, IntoResponse},
+    http::{StatusCode, header},
+    response::{Html, IntoResponse, Response},
 };
-use blastview::view::View;
+use blastview::view::{RenderableView, ViewError};
+use futures::{StreamExt, stream};
 use uuid::Uuid;
-use web::session::LiveSession;
+use web::session::{LiveSession, RenderChunk};
 
-use crate::state::AppState;
+use crate::{server::SsrMode, state::AppState};
 
-pub async fn ssr_handler<V, F>(State(state): State<Arc<AppState<V, F>>>) -> impl IntoResponse
+pub async fn ssr_handler<V, F>(State(state): State<Arc<AppState<V, F>>>) -> Response
 where
-    V: View + Send + Sync + 'static,
+    V: RenderableView + Send + Sync + 'static,
     F: Fn() -> V + Send + Sync,
 {
     let factory = Arc::clone(&state.factory);
     let session_id = Uuid::new_v4();
-    let session = LiveSession::new(|| factory());
-    let html = session.0.dynamic_render();
-    state.sessions.insert(session_id, session);
+    let (session, patch_rx) = LiveSession::with_policy(|| factory(), state.policy.clone());
+    let session = Arc::new(session);
     let hydration_script =
         include_str!("../js/script.js").replace("$SESSION_ID", &session_id.to_string());
 
-    let html = format!(
+    let html = match state.ssr_mode {
+        SsrMode::Streamed(timeout) => {
+            let mut chunks = Box::pin(Arc::clone(&session).render_stream(timeout));
+            let Some(RenderChunk::Page(html)) = chunks.next().await else {
+                unreachable!("a render stream starts with the page");
+            };
+            if let Some(error) = session.error() {
+                return error_response(&state, &error);
+            }
+            state.sessions.insert(session_id, (session, patch_rx));
+
+            // views are swapped in as their resources settle, before the page is hydrated
+            let start = document_start(&html, include_str!("../js/stream.js"));
+            let views = chunks.map(|chunk| match chunk {
+                RenderChunk::View { view_id, html } => format!(
+                    r#"<template data-bv-view="{view_id}">{html}</template><script>bvSwap("{view_id}")</script>"#
+                ),
+                RenderChunk::Page(_) => unreachable!("a render stream has a single page"),
+            });
+            let end = document_end(&hydration_script);
+            let body = stream::once(ready(start))
+                .chain(views)
+                .chain(stream::once(ready(end)))
+                .map(Ok::<_, Infallible>);
+
+            tracing::debug!("streaming ssr content");
+
+            return (
+                [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+                Body::from_stream(body),
+            )
+                .into_response();
+        }
+        SsrMode::Settled(timeout) => session.settled_render(timeout).await,
+        SsrMode::Immediate => session.dynamic_render(),
+    };
+    if let Some(error) = session.error() {
+        return error_response(&state, &error);
+    }
+    state.sessions.insert(session_id, (session, patch_rx));
+
+    tracing::debug!("serving ssr content");
+
+    Html(document_start(&html, "") + &document_end(&hydration_script)).into_response()
+}
+
+fn error_response<V, F>(state: &AppState<V, F>, error: &ViewError) -> Response
+where
+    V: RenderableView + Send + Sync + 'static,
+    F: Fn() -> V + Send + Sync + 'static,
+{
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Html((state.error_page)(error)),
+    )
+        .into_response()
+}
+
+fn document_start(html: &str, head_script: &str) -> String {
+    format!(
         r#"
             <!DOCTYPE html>
             <html>
             <head>
                 <title>BlastView App</title>
+                <script>
+                {head_script}
+                </script>
             </head>
             <body>
-                <div id="app">{}</div>
+                <div id="app">{html}</div>
+        "#
+    )
+}
+
+fn document_end(hydration_script: &str) -> String {
+    format!(
+        r#"
                 <script>
-                {}
+                {hydration_script}
                 </script>
             </body>
             </html>
-        "#,
-        html, hydration_script
-    );
-
-    tracing::debug!("serving ssr content");
-
-    Html(html)
+        "#
+    )
 }
@@ -46,6 +46,14 @@ function applyPatch(patch) {
         });
       }
       break;
+    case "SetText":
+      {
+        const elements = document.querySelectorAll(patch["selector"]);
+        elements.forEach((element) => {
+          element.textContent = patch["text"];
+        });
+      }
+      break;
     case "ReplaceChild":
       {
         const elements = document.querySelectorAll(patch["selector"]);
@@ -141,3 +149,9 @@ function cleanupEventListeners(element) {
 
 class View extends HTMLElement { }
 customElements.define("bv-view", View);
+
+class Signal extends HTMLElement { }
+customElements.define("bv-signal", Signal);
+
+class RawHtml extends HTMLElement { }
+customElements.define("bv-raw", RawHtml);
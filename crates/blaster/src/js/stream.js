@@ -0,0 +1,9 @@
+function bvSwap(viewId) {
+  const template = document.querySelector(`template[data-bv-view="${viewId}"]`);
+  const view = document.querySelector(`bv-view[data-view="${viewId}"]`);
+  if (view) {
+    view.replaceWith(template.content);
+  }
+  template.remove();
+  document.currentScript.remove();
+}
@@ -0,0 +1,104 @@
+use std::{io, marker::PhantomData, sync::Arc, time::Duration};
+
+use axum::Router;
+use blastview::view::{RenderableView, ViewError};
+use tokio::net::TcpListener;
+use web::policy::AttributePolicy;
+
+use crate::{handlers, state::AppState};
+
+/// Renders the page served when the root view fails during server-side rendering.
+pub type ErrorPage = Arc<dyn Fn(&ViewError) -> String + Send + Sync>;
+
+/// How server-side rendering treats resources that are still loading.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SsrMode {
+    /// Pages are served with their loading states.
+    Immediate,
+    /// Pages are served once their resources settle.
+    Settled(Duration),
+    /// Pages are served with their loading states, followed by the views that settle.
+    Streamed(Duration),
+}
+
+/// Serves a view, rendering it on the server and keeping it live over a WebSocket.
+pub struct Server<V, F> {
+    factory: F,
+    error_page: ErrorPage,
+    ssr_mode: SsrMode,
+    policy: AttributePolicy,
+    _view: PhantomData<fn() -> V>,
+}
+
+impl<V, F> Server<V, F>
+where
+    V: RenderableView + Send + Sync + 'static,
+    F: Fn() -> V + Send + Sync + 'static,
+{
+    pub fn new(factory: F) -> Self {
+        Self {
+            factory,
+            error_page: Arc::new(default_error_page),
+            ssr_mode: SsrMode::Immediate,
+            policy: AttributePolicy::default(),
+            _view: PhantomData,
+        }
+    }
+
+    /// Sets the page served, with a 500 status, for errors not caught by any error boundary.
+    pub fn error_page<P>(mut self, page: P) -> Self
+    where
+        P: Fn(&ViewError) -> String + Send + Sync + 'static,
+    {
+        self.error_page = Arc::new(page);
+        self
+    }
+
+    /// Waits up to `timeout` for the resources of a page to settle before serving it,
+    /// so that the initial HTML holds their data instead of loading placeholders.
+    pub fn await_resources(mut self, timeout: Duration) -> Self {
+        self.ssr_mode = SsrMode::Settled(timeout);
+        self
+    }
+
+    /// Serves the page right away, then streams the views whose resources settle within `timeout`
+    /// over the same response, each replacing its loading state.
+    pub fn stream_resources(mut self, timeout: Duration) -> Self {
+        self.ssr_mode = SsrMode::Streamed(timeout);
+        self
+    }
+
+    /// Sets which attributes reach the client, such as the URL schemes allowed in links.
+    pub fn attribute_policy(mut self, policy: AttributePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub async fn serve(self) -> io::Result<()> {
+        let state = AppState::new(self.factory, self.error_page, self.ssr_mode, self.policy);
+
+        let app = Router::new()
+            .merge(handlers::router::<V, F>())
+            .with_state(Arc::new(state));
+
+        let listener = TcpListener::bind(("0.0.0.0", 8080)).await?;
+        tracing::info!("Listening on 0.0.0.0:8080");
+        axum::serve(listener, app).await
+    }
+}
+
+// the error itself is logged, but not shown to the client
+fn default_error_page(_: &ViewError) -> String {
+    r#"
+        <!DOCTYPE html>
+        <html>
+        <head>
+            <title>Internal Server Error</title>
+        </head>
+        <body>
+            <h1>Internal Server Error</h1>
+        </body>
+        </html>
+    "#
+    .to_string()
+}
@@ -3,21 +3,37 @@ use std::sync::Arc;
 use blastview::view::RenderableView;
 use dashmap::DashMap;
 use uuid::Uuid;
-use web::session::{LiveSession, patch::Patch};
+use web::{
+    policy::AttributePolicy,
+    session::{LiveSession, patch::Patch},
+};
+
+use crate::server::{ErrorPage, SsrMode};
 
 pub struct AppState<V: RenderableView + Send + Sync + 'static, F: Fn() -> V + Send + Sync + 'static>
 {
     pub factory: Arc<F>,
-    pub sessions: DashMap<Uuid, (LiveSession, flume::Receiver<Patch>)>,
+    pub sessions: DashMap<Uuid, (Arc<LiveSession>, flume::Receiver<Patch>)>,
+    pub error_page: ErrorPage,
+    pub ssr_mode: SsrMode,
+    pub policy: AttributePolicy,
 }
 
 impl<V: RenderableView + Send + Sync + 'static, F: Fn() -> V + Send + Sync + 'static>
     AppState<V, F>
 {
-    pub fn new(factory: F) -> Self {
+    pub fn new(
+        factory: F,
+        error_page: ErrorPage,
+        ssr_mode: SsrMode,
+        policy: AttributePolicy,
+    ) -> Self {
         Self {
             factory: Arc::new(factory),
             sessions: Default::default(),
+            error_page,
+            ssr_mode,
+            policy,
         }
     }
 }
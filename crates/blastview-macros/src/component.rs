@@ -0,0 +1,98 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Attribute, DeriveInput, FnArg, ItemFn, Pat, parse_quote, spanned::Spanned};
+
+use crate::props;
+
+/// Expands a function component to a props struct, its builder, and a `View` implementation
+/// calling the function with the context and a clone of each prop.
+pub fn expand(function: &ItemFn) -> syn::Result<TokenStream> {
+    let signature = &function.sig;
+    if let Some(asyncness) = &signature.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span(),
+            "components cannot be async, use `cx.use_resource` instead",
+        ));
+    }
+
+    let mut inputs = signature.inputs.iter();
+    let Some(FnArg::Typed(_)) = inputs.next() else {
+        return Err(syn::Error::new(
+            signature.span(),
+            "components take the view context as their first argument",
+        ));
+    };
+
+    let mut fields = vec![];
+    let mut names = vec![];
+    for input in inputs {
+        let FnArg::Typed(input) = input else {
+            return Err(syn::Error::new(
+                input.span(),
+                "components cannot take `self`",
+            ));
+        };
+        let Pat::Ident(pat) = &*input.pat else {
+            return Err(syn::Error::new(
+                input.pat.span(),
+                "props must be named by a single identifier",
+            ));
+        };
+        let name = &pat.ident;
+        let ty = &input.ty;
+        let attrs = &input.attrs;
+        fields.push(quote!(#(#attrs)* pub #name: #ty));
+        names.push(name);
+    }
+
+    let vis = &function.vis;
+    let name = &signature.ident;
+    let docs: Vec<&Attribute> = function
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .collect();
+    let (impl_generics, ty_generics, where_clause) = signature.generics.split_for_impl();
+    let item: DeriveInput = if names.is_empty() {
+        parse_quote!(#vis struct #name #impl_generics #where_clause;)
+    } else {
+        parse_quote!(#vis struct #name #impl_generics #where_clause { #(#fields,)* })
+    };
+    let builder = props::derive(&item)?;
+
+    // the props are only read through the function, so their attributes can be dropped
+    let mut item = item;
+    if let syn::Data::Struct(data) = &mut item.data {
+        for field in data.fields.iter_mut() {
+            field.attrs.retain(|attr| !attr.path().is_ident("prop"));
+        }
+    }
+
+    let mut render = function.clone();
+    render.attrs.retain(|attr| !attr.path().is_ident("doc"));
+    render.vis = syn::Visibility::Inherited;
+    render.sig.ident = format_ident!("render");
+    for input in render.sig.inputs.iter_mut() {
+        if let FnArg::Typed(input) = input {
+            input.attrs.clear();
+        }
+    }
+
+    Ok(quote! {
+        #(#docs)*
+        #item
+
+        #builder
+
+        impl #impl_generics ::blastview::view::View for #name #ty_generics #where_clause {
+            fn render(
+                &self,
+                cx: &impl ::blastview::context::ViewContext,
+            ) -> impl ::core::convert::Into<::blastview::node::Node> {
+                #render
+
+                render(cx, #( ::core::clone::Clone::clone(&self.#names) ),*)
+            }
+        }
+    })
+}
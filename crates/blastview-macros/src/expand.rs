@@ -0,0 +1,146 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{Expr, Ident, spanned::Spanned};
+
+use crate::{
+    markup::{Attr, AttrKind, Component, Element, Markup, Nodes},
+    tags,
+};
+
+/// Expands markup to `Node` builder calls. Views can only be created when a context is given.
+pub fn expand(nodes: &Nodes, cx: Option<&Expr>) -> syn::Result<TokenStream> {
+    let nodes = expand_nodes(nodes, cx)?;
+    match cx {
+        // the context is used even if the markup creates no view
+        Some(cx) => Ok(quote!({
+            let _ = &#cx;
+            #nodes
+        })),
+        None => Ok(nodes),
+    }
+}
+
+fn expand_nodes(nodes: &Nodes, cx: Option<&Expr>) -> syn::Result<TokenStream> {
+    match &nodes.0[..] {
+        [Markup::Text(text)] => Ok(quote!(::blastview::node::Node::from(#text))),
+        [Markup::Spread(nodes)] => Ok(quote!(::blastview::node::Node::fragment(#nodes))),
+        [node] => expand_node(node, cx),
+        nodes => {
+            let nodes = nodes
+                .iter()
+                .map(|node| expand_node(node, cx))
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote! {
+                ::blastview::node::Node::fragment([
+                    #( ::core::convert::Into::<::blastview::node::Node>::into(#nodes) ),*
+                ])
+            })
+        }
+    }
+}
+
+fn expand_node(node: &Markup, cx: Option<&Expr>) -> syn::Result<TokenStream> {
+    match node {
+        Markup::Element(element) => expand_element(element, cx),
+        Markup::Component(component) => expand_component(component, cx),
+        Markup::Text(text) => Ok(quote!(#text)),
+        Markup::Expr(expr) => Ok(quote!(#expr)),
+        Markup::Spread(nodes) => Ok(quote!(::blastview::node::Node::fragment(#nodes))),
+    }
+}
+
+fn expand_element(element: &Element, cx: Option<&Expr>) -> syn::Result<TokenStream> {
+    let tag = &element.tag;
+    let attrs = element.attrs.iter().map(expand_attr);
+    let children = element
+        .children
+        .iter()
+        .map(|child| match child {
+            Markup::Spread(nodes) => Ok(quote!(.children(#nodes))),
+            child => {
+                let child = expand_node(child, cx)?;
+                Ok(quote!(.child(#child)))
+            }
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote_spanned! {element.span=>
+        ::blastview::node::Node::new(#tag) #(#attrs)* #(#children)*
+    })
+}
+
+fn expand_attr(attr: &Attr) -> TokenStream {
+    let name = &attr.name;
+    match &attr.kind {
+        AttrKind::Static(value) => quote!(.attr(#name, #value)),
+        AttrKind::Dynamic(value) if tags::is_boolean_attribute(name) => {
+            quote!(.flag(#name, #value))
+        }
+        AttrKind::Dynamic(value) => quote_spanned! {value.span()=>
+            .attr(#name, &::std::string::ToString::to_string(&(#value)))
+        },
+        AttrKind::Flag => quote!(.flag(#name, true)),
+        AttrKind::Event {
+            is_async: false,
+            handler,
+        } => quote!(.on(#name, #handler)),
+        AttrKind::Event {
+            is_async: true,
+            handler,
+        } => quote!(.on_async(#name, #handler)),
+        AttrKind::Bind(signal) => quote!(.bind(#name, #signal)),
+    }
+}
+
+fn expand_component(component: &Component, cx: Option<&Expr>) -> syn::Result<TokenStream> {
+    let path = &component.path;
+    let Some(cx) = cx else {
+        return Err(syn::Error::new(
+            path.span(),
+            "views can only be created inside `view!(cx, ...)`",
+        ));
+    };
+
+    // values are computed once, and cloned whenever the view is created
+    let names = component.fields.iter().map(|(name, _)| prop_name(name));
+    let bindings: Vec<_> = component
+        .fields
+        .iter()
+        .map(|(name, _)| format_ident!("__blastview_{}", name))
+        .collect();
+    let values = component.fields.iter().map(|(_, value)| value);
+
+    let create = if component.uses_builder {
+        quote_spanned! {path.span()=>
+            {
+                // a plain path lets the generics of the view be inferred
+                use ::blastview::view::Props as _;
+                #path::builder()
+                    #( .#names(::core::clone::Clone::clone(&#bindings)) )*
+                    .build()
+            }
+        }
+    } else {
+        quote_spanned! {path.span()=>
+            #path {
+                #( #names: ::core::convert::Into::into(::core::clone::Clone::clone(&#bindings)), )*
+            }
+        }
+    };
+
+    // hook diagnostics point at the view's tag
+    Ok(quote_spanned! {path.span()=>
+        {
+            #( let #bindings = #values; )*
+            ::blastview::context::ViewContext::create_view(#cx, move || #create)
+        }
+    })
+}
+
+/// Props named like keywords, such as `type`, are set through raw identifiers.
+fn prop_name(name: &Ident) -> Ident {
+    match syn::parse_str::<Ident>(&name.to_string()) {
+        Ok(_) => name.clone(),
+        Err(_) => Ident::new_raw(&name.to_string(), name.span()),
+    }
+}
@@ -0,0 +1,199 @@
+mod component;
+mod expand;
+mod markup;
+mod props;
+mod tags;
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, Expr, ItemFn, Token, parse::Parse, parse_macro_input};
+
+use crate::markup::Nodes;
+
+/// Writes a node tree with HTML-like syntax, expanding to [`Node`] builder calls.
+///
+/// ```
+/// use blastview::{html, node::Node};
+///
+/// let items = ["one", "two"];
+/// let note: Option<&str> = None;
+/// let node: Node = html! {
+///     <ul class="list" data-count={items.len()}>
+///         {..items.iter().map(|item| html! { <li>{item}</li> })}
+///     </ul>
+///     <input type="checkbox" checked disabled={items.is_empty()} />
+///     <button on:click={|| println!("clicked")}>"Click"</button>
+///     {..note}
+/// }
+/// .into();
+/// ```
+///
+/// - `name="value"` and `name={expr}` set attributes, `name` alone sets a boolean attribute,
+///   and boolean attributes such as `disabled={expr}` take a `bool`.
+/// - `on:event={handler}`, `on_async:event={handler}` and `bind:name={signal}` map to
+///   `on`, `on_async` and `bind`.
+/// - `"text"` and `{expr}` are single children, and `{..expr}` adds every node of an iterator
+///   or an `Option`.
+/// - Several root nodes make a fragment.
+///
+/// Unknown tags, mismatched end tags and malformed attributes are compile errors:
+///
+/// ```compile_fail
+/// blastview::html! { <dvi></dvi> };
+/// ```
+///
+/// ```compile_fail
+/// blastview::html! { <div></span> };
+/// ```
+///
+/// ```compile_fail
+/// blastview::html! { <button onclick="alert(1)"></button> };
+/// ```
+///
+/// [`Node`]: https://docs.rs/blastview/latest/blastview/node/enum.Node.html
+#[proc_macro]
+pub fn html(input: TokenStream) -> TokenStream {
+    let nodes = parse_macro_input!(input as Nodes);
+    expand::expand(&nodes, None)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Like [`html!`], but also creates child views from tags named like types,
+/// given the context of the view being rendered.
+///
+/// ```
+/// use blastview::{component, context::ViewContext, node::Node, view, view::View};
+///
+/// #[component]
+/// fn Counter(cx: &impl ViewContext, label: String, #[prop(default)] start: i32) -> impl Into<Node> {
+///     let (count, set_count) = cx.use_state(start);
+///     view!(cx,
+///         <button on:click={move || set_count(count + 1)}>
+///             {format!("{label}: {count}")}
+///         </button>
+///     )
+/// }
+///
+/// struct Title {
+///     text: String,
+/// }
+///
+/// impl View for Title {
+///     fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+///         view!(cx, <h1>{self.text.clone()}</h1>)
+///     }
+/// }
+///
+/// struct App;
+///
+/// impl View for App {
+///     fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+///         view!(cx,
+///             <main>
+///                 <Title text="Counters" />
+///                 <Counter label="first" .. />
+///                 <Counter start={10} label="second" .. />
+///             </main>
+///         )
+///     }
+/// }
+/// ```
+///
+/// Each view tag expands to a struct literal, `cx.create_view(|| View { field: value.into() })`.
+/// Ending the tag with `..` builds the view through its [`Props`] builder instead, so that the
+/// props left out take their defaults: `cx.create_view(|| View::builder().field(value).build())`.
+/// Field values are computed on every render, but only used when the view is first created.
+///
+/// ```compile_fail
+/// use blastview::{context::ViewContext, node::Node, view::View};
+///
+/// struct Counter;
+///
+/// impl View for Counter {
+///     fn render(&self, _: &impl ViewContext) -> impl Into<Node> {
+///         blastview::html! { <p></p> }
+///     }
+/// }
+///
+/// // views need a context
+/// let node = blastview::html! { <Counter /> };
+/// ```
+///
+/// [`Props`]: derive@Props
+#[proc_macro]
+pub fn view(input: TokenStream) -> TokenStream {
+    let ViewInput { cx, nodes } = parse_macro_input!(input as ViewInput);
+    expand::expand(&nodes, Some(&cx))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct ViewInput {
+    cx: Expr,
+    nodes: Nodes,
+}
+
+impl Parse for ViewInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let cx = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let nodes = input.parse()?;
+        Ok(Self { cx, nodes })
+    }
+}
+
+/// Derives [`Props`] for a struct, see the trait for the field attributes.
+///
+/// [`Props`]: https://docs.rs/blastview/latest/blastview/view/trait.Props.html
+#[proc_macro_derive(Props, attributes(prop))]
+pub fn derive_props(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    props::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Turns a function into a view, whose props are the function's arguments after the context.
+///
+/// ```
+/// use blastview::{component, context::ViewContext, html, node::Node, view::Props};
+///
+/// /// A greeting for `name`.
+/// #[component]
+/// pub fn Greeting(
+///     _cx: &impl ViewContext,
+///     name: String,
+///     #[prop(optional)] punctuation: Option<char>,
+/// ) -> Node {
+///     let punctuation = punctuation.unwrap_or('!');
+///     html! { <p>{format!("Hello, {name}{punctuation}")}</p> }.into()
+/// }
+///
+/// let greeting = Greeting::builder().name("world").build();
+/// assert_eq!(greeting.name, "world");
+/// ```
+///
+/// This generates a `Greeting` struct with a public field per prop, its [`Props`] builder, and
+/// a `View` implementation calling the function with a clone of each prop on every render.
+/// Props take the same `#[prop(...)]` attributes as `#[derive(Props)]`.
+///
+/// [`Props`]: derive@Props
+#[proc_macro_attribute]
+pub fn component(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return syn::Error::new(
+            proc_macro2::TokenStream::from(args)
+                .into_iter()
+                .next()
+                .unwrap()
+                .span(),
+            "`#[component]` takes no arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let function = parse_macro_input!(input as ItemFn);
+    component::expand(&function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,354 @@
+use proc_macro2::Span;
+use syn::{
+    Expr, Ident, LitStr, Path, Token, braced,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    token::Brace,
+};
+
+use crate::tags;
+
+/// A node of markup, as written inside `html!` or `view!`.
+pub enum Markup {
+    Element(Element),
+    Component(Component),
+    Text(LitStr),
+    /// `{expr}`, a single child.
+    Expr(Expr),
+    /// `{..expr}`, any number of children from an iterator or an `Option`.
+    Spread(Expr),
+}
+
+pub struct Element {
+    pub tag: String,
+    pub span: Span,
+    pub attrs: Vec<Attr>,
+    pub children: Vec<Markup>,
+}
+
+pub struct Attr {
+    pub name: String,
+    pub span: Span,
+    pub kind: AttrKind,
+}
+
+pub enum AttrKind {
+    /// `name="value"`
+    Static(LitStr),
+    /// `name={expr}`, or `name={bool}` for boolean attributes.
+    Dynamic(Expr),
+    /// `name`, a boolean attribute that is set.
+    Flag,
+    /// `on:event={handler}` or `on_async:event={handler}`
+    Event { is_async: bool, handler: Expr },
+    /// `bind:name={signal}`
+    Bind(Expr),
+}
+
+/// `<MyView field={value} />`, a child view created from a struct literal, or
+/// `<MyView field={value} .. />`, created through its props builder.
+pub struct Component {
+    pub path: Path,
+    pub fields: Vec<(Ident, Expr)>,
+    pub uses_builder: bool,
+}
+
+/// Every node at the root of a macro invocation.
+pub struct Nodes(pub Vec<Markup>);
+
+impl Parse for Nodes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut nodes = vec![];
+        while !input.is_empty() {
+            if input.peek(Token![<]) && input.peek2(Token![/]) {
+                return Err(input.error("closing tag without a matching opening tag"));
+            }
+            nodes.push(input.parse()?);
+        }
+        if nodes.is_empty() {
+            return Err(input.error("expected markup"));
+        }
+        Ok(Self(nodes))
+    }
+}
+
+impl Parse for Markup {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(Self::Text(input.parse()?));
+        }
+
+        if input.peek(Brace) {
+            let content;
+            braced!(content in input);
+            if content.peek(Token![..]) {
+                content.parse::<Token![..]>()?;
+                return Ok(Self::Spread(content.parse()?));
+            }
+            return Ok(Self::Expr(content.parse()?));
+        }
+
+        if !input.peek(Token![<]) {
+            return Err(input.error(r#"expected a tag, a string literal or a `{...}` block"#));
+        }
+
+        // components are named like types, elements are not
+        let fork = input.fork();
+        fork.parse::<Token![<]>()?;
+        let is_component = fork.parse::<Path>().is_ok_and(|path| {
+            path.segments
+                .last()
+                .is_some_and(|segment| segment.ident.to_string().starts_with(char::is_uppercase))
+        });
+        if is_component {
+            Ok(Self::Component(input.parse()?))
+        } else {
+            Ok(Self::Element(input.parse()?))
+        }
+    }
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let (tag, span) = parse_name(input)?;
+        if !tags::is_known(&tag) {
+            return Err(syn::Error::new(
+                span,
+                format!("unknown tag `<{tag}>`, custom elements must contain a dash"),
+            ));
+        }
+
+        let mut attrs: Vec<Attr> = vec![];
+        while !input.peek(Token![>]) && !input.peek(Token![/]) {
+            if input.is_empty() {
+                return Err(syn::Error::new(span, format!("unclosed tag `<{tag}>`")));
+            }
+            let attr: Attr = input.parse()?;
+            if attrs.iter().any(|existing| {
+                existing.name == attr.name && existing.is_event() == attr.is_event()
+            }) {
+                return Err(syn::Error::new(
+                    attr.span,
+                    format!("duplicate attribute `{}`", attr.name),
+                ));
+            }
+            attrs.push(attr);
+        }
+
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+            return Ok(Self {
+                tag,
+                span,
+                attrs,
+                children: vec![],
+            });
+        }
+        input.parse::<Token![>]>()?;
+
+        // void elements have no end tag, whether written `<br>` or `<br />`
+        if tags::is_void(&tag) {
+            return Ok(Self {
+                tag,
+                span,
+                attrs,
+                children: vec![],
+            });
+        }
+
+        let mut children = vec![];
+        loop {
+            if input.is_empty() {
+                return Err(syn::Error::new(span, format!("unclosed tag `<{tag}>`")));
+            }
+            if input.peek(Token![<]) && input.peek2(Token![/]) {
+                break;
+            }
+            children.push(input.parse()?);
+        }
+
+        input.parse::<Token![<]>()?;
+        input.parse::<Token![/]>()?;
+        let (closing, closing_span) = parse_name(input)?;
+        if closing != tag {
+            return Err(syn::Error::new(
+                closing_span,
+                format!("expected `</{tag}>`, found `</{closing}>`"),
+            ));
+        }
+        input.parse::<Token![>]>()?;
+
+        Ok(Self {
+            tag,
+            span,
+            attrs,
+            children,
+        })
+    }
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (name, span) = parse_name(input)?;
+        let error = |message: String| Err(syn::Error::new(span, message));
+
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            if input.peek(LitStr) {
+                Some(AttrValue::Static(input.parse()?))
+            } else if input.peek(Brace) {
+                let content;
+                braced!(content in input);
+                Some(AttrValue::Dynamic(content.parse()?))
+            } else {
+                return error(format!(
+                    r#"the value of `{name}` must be a string literal or a `{{...}}` block"#
+                ));
+            }
+        } else {
+            None
+        };
+
+        let kind = match (name.split_once(':'), value) {
+            (Some((namespace @ ("on" | "on_async"), event)), Some(AttrValue::Dynamic(handler))) => {
+                let kind = AttrKind::Event {
+                    is_async: namespace == "on_async",
+                    handler,
+                };
+                return Ok(Self {
+                    name: event.to_string(),
+                    span,
+                    kind,
+                });
+            }
+            (Some(("on" | "on_async", _)), _) => {
+                return error(format!(
+                    "`{name}` takes a handler: `{name}={{move || ...}}`"
+                ));
+            }
+            (Some(("bind", attr)), Some(AttrValue::Dynamic(signal))) => {
+                return Ok(Self {
+                    name: attr.to_string(),
+                    span,
+                    kind: AttrKind::Bind(signal),
+                });
+            }
+            (Some(("bind", _)), _) => {
+                return error(format!("`{name}` takes a signal: `{name}={{signal}}`"));
+            }
+            (Some((namespace, _)), _) if !matches!(namespace, "xlink" | "xml" | "xmlns") => {
+                return error(format!(
+                    "unknown attribute namespace `{namespace}`, expected `on`, `on_async` or `bind`"
+                ));
+            }
+            _ if name.starts_with("on") && name.len() > 2 => {
+                return error(format!(
+                    "event handlers are attached with `on:{}={{...}}`",
+                    &name[2..]
+                ));
+            }
+            (_, Some(AttrValue::Static(value))) => AttrKind::Static(value),
+            (_, Some(AttrValue::Dynamic(value))) => AttrKind::Dynamic(value),
+            (_, None) => AttrKind::Flag,
+        };
+
+        Ok(Self { name, span, kind })
+    }
+}
+
+impl Attr {
+    fn is_event(&self) -> bool {
+        matches!(self.kind, AttrKind::Event { .. })
+    }
+}
+
+enum AttrValue {
+    Static(LitStr),
+    Dynamic(Expr),
+}
+
+impl Parse for Component {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let path: Path = input.parse()?;
+
+        let mut fields: Vec<(Ident, Expr)> = vec![];
+        let mut uses_builder = false;
+        while !input.peek(Token![/]) {
+            if input.peek(Token![..]) {
+                let rest = input.parse::<Token![..]>()?;
+                if !input.peek(Token![/]) {
+                    return Err(syn::Error::new(
+                        rest.span(),
+                        "`..` comes after every field, right before `/>`",
+                    ));
+                }
+                uses_builder = true;
+                continue;
+            }
+            if input.peek(Token![>]) {
+                return Err(syn::Error::new(
+                    path.span(),
+                    "views take no children, close them with `/>`",
+                ));
+            }
+            if input.is_empty() {
+                return Err(syn::Error::new(path.span(), "unclosed view tag"));
+            }
+
+            let field: Ident = input.call(Ident::parse_any)?;
+            input.parse::<Token![=]>()?;
+            let value = if input.peek(LitStr) {
+                Expr::Lit(input.parse()?)
+            } else if input.peek(Brace) {
+                let content;
+                braced!(content in input);
+                content.parse()?
+            } else {
+                return Err(input.error(format!(
+                    r#"the value of `{field}` must be a string literal or a `{{...}}` block"#
+                )));
+            };
+
+            if fields.iter().any(|(existing, _)| *existing == field) {
+                return Err(syn::Error::new(
+                    field.span(),
+                    format!("duplicate field `{field}`"),
+                ));
+            }
+            fields.push((field, value));
+        }
+        input.parse::<Token![/]>()?;
+        input.parse::<Token![>]>()?;
+
+        Ok(Self {
+            path,
+            fields,
+            uses_builder,
+        })
+    }
+}
+
+/// Parses a tag or attribute name, such as `div`, `my-widget`, `data-id` or `on:click`.
+fn parse_name(input: ParseStream) -> syn::Result<(String, Span)> {
+    let first = input.call(Ident::parse_any)?;
+    let span = first.span();
+    let mut name = first.unraw().to_string();
+
+    while input.peek(Token![-]) || (input.peek(Token![:]) && !input.peek(Token![::])) {
+        if input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            name.push('-');
+        } else {
+            input.parse::<Token![:]>()?;
+            name.push(':');
+        }
+        let part = input.call(Ident::parse_any)?;
+        name.push_str(&part.unraw().to_string());
+    }
+
+    Ok((name, span))
+}
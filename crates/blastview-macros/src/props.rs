@@ -0,0 +1,307 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, Data, DeriveInput, Expr, Fields, GenericArgument, GenericParam, Ident,
+    PathArguments, Type, ext::IdentExt, spanned::Spanned,
+};
+
+/// How a prop is filled when the builder does not set it.
+enum Fallback {
+    /// The view cannot be built until the prop is set.
+    Required,
+    /// `#[prop(default)]` or `#[prop(default = expr)]`
+    Default(Option<Expr>),
+    /// `#[prop(optional)]` on an `Option<T>`, set from a `T`.
+    Optional(Type),
+}
+
+struct Prop {
+    name: Ident,
+    ty: Type,
+    docs: Vec<Attribute>,
+    fallback: Fallback,
+}
+
+/// Expands `#[derive(Props)]` to a builder whose `build` is only available once every
+/// required prop is set.
+pub fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "props can only be derived for structs",
+        ));
+    };
+    let (props, is_unit) = match &data.fields {
+        Fields::Named(fields) => (
+            fields
+                .named
+                .iter()
+                .map(|field| {
+                    Ok(Prop {
+                        name: field.ident.clone().unwrap(),
+                        ty: field.ty.clone(),
+                        docs: docs(&field.attrs),
+                        fallback: fallback(&field.attrs, &field.ty)?,
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?,
+            false,
+        ),
+        Fields::Unit => (vec![], true),
+        Fields::Unnamed(fields) => {
+            return Err(syn::Error::new(
+                fields.span(),
+                "props need named fields, or none at all",
+            ));
+        }
+    };
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let builder = format_ident!("{}Builder", name);
+
+    // the builder takes the generics of the props, without defaults, then one parameter per
+    // required prop, which is `()` until the prop is set
+    let mut params: Vec<GenericParam> = input.generics.params.iter().cloned().collect();
+    for param in &mut params {
+        match param {
+            GenericParam::Type(param) => {
+                param.eq_token = None;
+                param.default = None;
+            }
+            GenericParam::Const(param) => {
+                param.eq_token = None;
+                param.default = None;
+            }
+            GenericParam::Lifetime(_) => {}
+        }
+    }
+    let args: Vec<TokenStream> = params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                quote!(#ident)
+            }
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                quote!(#lifetime)
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                quote!(#ident)
+            }
+        })
+        .collect();
+    let where_clause = &input.generics.where_clause;
+
+    let required: Vec<usize> = props
+        .iter()
+        .enumerate()
+        .filter(|(_, prop)| matches!(prop.fallback, Fallback::Required))
+        .map(|(i, _)| i)
+        .collect();
+    let states: Vec<Ident> = required
+        .iter()
+        .map(|&i| format_ident!("__{}", props[i].name.unraw().to_string().to_uppercase()))
+        .collect();
+    let state_of = |i: usize| required.iter().position(|&r| r == i);
+
+    let names: Vec<&Ident> = props.iter().map(|prop| &prop.name).collect();
+    let storage = props
+        .iter()
+        .enumerate()
+        .map(|(i, prop)| match &prop.fallback {
+            Fallback::Required => {
+                let state = &states[state_of(i).unwrap()];
+                quote!(#state)
+            }
+            Fallback::Default(_) => {
+                let ty = &prop.ty;
+                quote!(::core::option::Option<#ty>)
+            }
+            Fallback::Optional(_) => {
+                let ty = &prop.ty;
+                quote!(#ty)
+            }
+        });
+    let initial = props.iter().map(|prop| match &prop.fallback {
+        Fallback::Required => quote!(()),
+        _ => quote!(::core::option::Option::None),
+    });
+    let unset = states.iter().map(|_| quote!(()));
+
+    let setters = props.iter().enumerate().map(|(i, prop)| {
+        let field = &prop.name;
+        let docs = &prop.docs;
+        match &prop.fallback {
+            Fallback::Required => {
+                let position = state_of(i).unwrap();
+                let ty = &prop.ty;
+                let others = states
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != position)
+                    .map(|(_, state)| state);
+                let before = states.iter().enumerate().map(|(j, state)| {
+                    if j == position {
+                        quote!(())
+                    } else {
+                        quote!(#state)
+                    }
+                });
+                let after = states.iter().enumerate().map(|(j, state)| {
+                    if j == position {
+                        quote!(#ty)
+                    } else {
+                        quote!(#state)
+                    }
+                });
+                let moved = names.iter().map(|other| {
+                    if *other == field {
+                        quote!(#other: ::core::convert::Into::into(value))
+                    } else {
+                        quote!(#other: self.#other)
+                    }
+                });
+                quote! {
+                    impl<#(#params,)* #(#others,)*> #builder<#(#args,)* #(#before,)*> #where_clause {
+                        #(#docs)*
+                        #vis fn #field(
+                            self,
+                            value: impl ::core::convert::Into<#ty>,
+                        ) -> #builder<#(#args,)* #(#after,)*> {
+                            #builder {
+                                #(#moved,)*
+                                __props: ::core::marker::PhantomData,
+                            }
+                        }
+                    }
+                }
+            }
+            fallback => {
+                let ty = match fallback {
+                    Fallback::Optional(inner) => inner,
+                    _ => &prop.ty,
+                };
+                quote! {
+                    impl<#(#params,)* #(#states,)*> #builder<#(#args,)* #(#states,)*> #where_clause {
+                        #(#docs)*
+                        #vis fn #field(mut self, value: impl ::core::convert::Into<#ty>) -> Self {
+                            self.#field = ::core::option::Option::Some(
+                                ::core::convert::Into::into(value),
+                            );
+                            self
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    let values = props.iter().map(|prop| {
+        let field = &prop.name;
+        match &prop.fallback {
+            Fallback::Required | Fallback::Optional(_) => quote!(#field: self.#field),
+            Fallback::Default(None) => {
+                quote!(#field: self.#field.unwrap_or_default())
+            }
+            Fallback::Default(Some(default)) => {
+                quote!(#field: self.#field.unwrap_or_else(|| #default))
+            }
+        }
+    });
+    let built = if is_unit {
+        quote!(#name)
+    } else {
+        quote!(#name { #(#values,)* })
+    };
+    let complete = required.iter().map(|&i| &props[i].ty);
+
+    let builder_docs = format!("Builds [`{name}`], see [`Props`](::blastview::view::Props).");
+    Ok(quote! {
+        #[doc = #builder_docs]
+        #[must_use]
+        #vis struct #builder<#(#params,)* #(#states,)*> #where_clause {
+            #(#names: #storage,)*
+            __props: ::core::marker::PhantomData<fn() -> #name<#(#args,)*>>,
+        }
+
+        impl<#(#params,)*> ::blastview::view::Props for #name<#(#args,)*> #where_clause {
+            type Builder = #builder<#(#args,)* #(#unset,)*>;
+
+            fn builder() -> Self::Builder {
+                #builder {
+                    #(#names: #initial,)*
+                    __props: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        #(#setters)*
+
+        impl<#(#params,)*> #builder<#(#args,)* #(#complete,)*> #where_clause {
+            #vis fn build(self) -> #name<#(#args,)*> {
+                #built
+            }
+        }
+    })
+}
+
+fn docs(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .cloned()
+        .collect()
+}
+
+fn fallback(attrs: &[Attribute], ty: &Type) -> syn::Result<Fallback> {
+    let mut fallback = Fallback::Required;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("prop")) {
+        attr.parse_nested_meta(|meta| {
+            if !matches!(fallback, Fallback::Required) {
+                return Err(meta.error("a prop takes a single fallback"));
+            }
+            if meta.path.is_ident("default") {
+                let default = if meta.input.peek(syn::Token![=]) {
+                    Some(meta.value()?.parse()?)
+                } else {
+                    None
+                };
+                fallback = Fallback::Default(default);
+                Ok(())
+            } else if meta.path.is_ident("optional") {
+                let Some(inner) = option_inner(ty) else {
+                    return Err(syn::Error::new(
+                        ty.span(),
+                        "optional props must be an `Option<T>`",
+                    ));
+                };
+                fallback = Fallback::Optional(inner.clone());
+                Ok(())
+            } else {
+                Err(meta.error("expected `default`, `default = expr` or `optional`"))
+            }
+        })?;
+    }
+    Ok(fallback)
+}
+
+/// The `T` of an `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
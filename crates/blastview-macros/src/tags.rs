@@ -0,0 +1,15 @@
+use blastview_tags::{BOOLEAN_ATTRIBUTES, HTML_TAGS, SVG_TAGS, VOID_TAGS};
+
+/// Whether a tag is an HTML or SVG element, or a custom element, whose name contains a dash.
+pub fn is_known(tag: &str) -> bool {
+    HTML_TAGS.contains(&tag) || SVG_TAGS.contains(&tag) || tag.contains('-')
+}
+
+pub fn is_void(tag: &str) -> bool {
+    VOID_TAGS.contains(&tag)
+}
+
+/// Whether an attribute is either present or absent, set from a `bool`.
+pub fn is_boolean_attribute(attr: &str) -> bool {
+    BOOLEAN_ATTRIBUTES.contains(&attr)
+}
@@ -0,0 +1,114 @@
+use std::{
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use uuid::Uuid;
+
+use crate::{rendering::RenderingQueue, view::ViewError};
+
+/// Catches the errors of every view created below the view that registered it.
+///
+/// See [`crate::view::ErrorBoundary`].
+#[derive(Clone)]
+pub struct Boundary {
+    inner: Arc<BoundaryInner>,
+}
+
+struct BoundaryInner {
+    // the view re-rendered when the boundary changes, if it was registered by one
+    owner: Option<(Uuid, Arc<RenderingQueue>)>,
+    error: Mutex<Option<ViewError>>,
+    // set when an error is caught, until the boundary view re-renders
+    has_caught: AtomicBool,
+}
+
+impl Boundary {
+    pub(crate) fn new(view_id: Uuid, rendering_queue: Arc<RenderingQueue>) -> Self {
+        Self::with_owner(Some((view_id, rendering_queue)))
+    }
+
+    /// Creates a boundary that is not registered by a view, to catch the errors that reach the root.
+    ///
+    /// See [`crate::context::Context::with_boundary`].
+    pub fn root() -> Self {
+        Self::with_owner(None)
+    }
+
+    fn with_owner(owner: Option<(Uuid, Arc<RenderingQueue>)>) -> Self {
+        Self {
+            inner: Arc::new(BoundaryInner {
+                owner,
+                error: Default::default(),
+                has_caught: Default::default(),
+            }),
+        }
+    }
+
+    /// The first error caught since the boundary was last reset.
+    pub fn error(&self) -> Option<ViewError> {
+        self.inner.error.lock().unwrap().clone()
+    }
+
+    /// Clears the caught error, re-creating the views below the boundary.
+    pub fn reset(&self) {
+        if self.inner.error.lock().unwrap().take().is_some() {
+            self.notify_owner();
+        }
+    }
+
+    fn catch(&self, error: ViewError) {
+        let mut caught = self.inner.error.lock().unwrap();
+        if caught.is_none() {
+            *caught = Some(error);
+            self.inner.has_caught.store(true, Ordering::Relaxed);
+            drop(caught);
+            self.notify_owner();
+        }
+    }
+
+    fn notify_owner(&self) {
+        if let Some((view_id, rendering_queue)) = &self.inner.owner {
+            rendering_queue.enqueue(*view_id);
+        }
+    }
+
+    /// Returns true if an error was caught since the last call.
+    pub(crate) fn take_caught(&self) -> bool {
+        self.inner.has_caught.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// Two boundaries are equal if they were registered by the same hook.
+impl PartialEq for Boundary {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+/// Logs the error and hands it to the boundary, if the failing view is inside one.
+pub(crate) fn report(boundary: Option<&Boundary>, error: ViewError) {
+    tracing::error!(view = error.view(), "{}", error.message());
+
+    if let Some(boundary) = boundary {
+        boundary.catch(error);
+    }
+}
+
+/// Runs `f`, reporting a panic as an error of the given view.
+pub(crate) fn catch<R>(
+    boundary: Option<&Boundary>,
+    view: &'static str,
+    f: impl FnOnce() -> R,
+) -> Option<R> {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => Some(result),
+        Err(payload) => {
+            report(boundary, ViewError::from_panic(view, payload));
+            None
+        }
+    }
+}
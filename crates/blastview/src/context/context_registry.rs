@@ -19,6 +19,10 @@ impl ContextRegistry {
         self.mapping.get(id).map(|val| Arc::clone(&val))
     }
 
+    pub fn remove(&self, id: &Uuid) {
+        self.mapping.remove(id);
+    }
+
     pub fn clear(&self) {
         self.mapping.clear();
     }
@@ -1,6 +1,6 @@
 use uuid::Uuid;
 
-use crate::node::Node;
+use crate::{node::Node, signal::Bindable};
 
 #[derive(Clone)]
 pub enum NodePatch {
@@ -30,6 +30,10 @@ pub enum NodePatch {
         node_id: Uuid,
         attr: String,
     },
+    SetText {
+        node_id: Uuid,
+        text: String,
+    },
     AttachEvent {
         node_id: Uuid,
         event: String,
@@ -41,6 +45,18 @@ pub enum NodePatch {
 }
 
 pub fn diff(from_node: Node, to_node: &mut Node, parent_id: Uuid, idx: usize) -> Vec<NodePatch> {
+    // fragments only appear as the root of a view, whose children are then diffed as a list
+    if matches!(from_node, Node::Fragment(_)) || matches!(to_node, Node::Fragment(_)) {
+        let from = match from_node {
+            Node::Fragment(fragment) => fragment.0,
+            node => vec![node],
+        };
+        return match to_node {
+            Node::Fragment(to) => diff_children(from, &mut to.0, parent_id),
+            to => diff_children(from, std::slice::from_mut(to), parent_id),
+        };
+    }
+
     match from_node {
         Node::Element(from) => match to_node {
             Node::Element(to) => {
@@ -81,6 +97,33 @@ pub fn diff(from_node: Node, to_node: &mut Node, parent_id: Uuid, idx: usize) ->
                     }
                 }
 
+                for (attr, signal) in from.bindings.iter() {
+                    if let Some(to_signal) = to.bindings.get(attr) {
+                        if changed(signal.as_ref(), to_signal.as_ref()) {
+                            patches.push(NodePatch::SetAttr {
+                                node_id: from.id,
+                                attr: attr.to_string(),
+                                value: to_signal.render(),
+                            });
+                        }
+                    } else if !to.attrs.contains_key(attr) {
+                        patches.push(NodePatch::RemoveAttr {
+                            node_id: from.id,
+                            attr: attr.to_string(),
+                        });
+                    }
+                }
+
+                for (attr, signal) in to.bindings.iter() {
+                    if !from.bindings.contains_key(attr) {
+                        patches.push(NodePatch::SetAttr {
+                            node_id: from.id,
+                            attr: attr.to_string(),
+                            value: signal.render(),
+                        });
+                    }
+                }
+
                 for (event, _) in from.events.iter() {
                     if !to.events.contains_key(event) {
                         patches.push(NodePatch::DetachEvent {
@@ -99,22 +142,7 @@ pub fn diff(from_node: Node, to_node: &mut Node, parent_id: Uuid, idx: usize) ->
                     }
                 }
 
-                let parent_id = from.id;
-
-                let patches = (*from)
-                    .children
-                    .into_iter()
-                    .zip(
-                        (match to_node {
-                            Node::Element(node) => node,
-                            _ => unreachable!(),
-                        })
-                        .children
-                        .iter_mut(),
-                    )
-                    .enumerate()
-                    .flat_map(|(idx, (from, to))| diff(from, to, parent_id, idx))
-                    .collect();
+                patches.extend(diff_children(from.children, &mut to.children, from.id));
 
                 patches
             }
@@ -124,24 +152,80 @@ pub fn diff(from_node: Node, to_node: &mut Node, parent_id: Uuid, idx: usize) ->
             }],
         },
         Node::Text(from) => match to_node {
-            Node::Element(_) => vec![NodePatch::ReplaceChild {
+            Node::Text(to) if from.0 == to.0 => vec![],
+            _ => vec![NodePatch::ReplaceChild {
                 node_id: parent_id,
                 child_idx: idx,
                 node: to_node.clone(),
             }],
-            Node::Text(to) => {
-                if from.0 != to.0 {
-                    vec![NodePatch::ReplaceChild {
-                        node_id: parent_id,
-                        child_idx: idx,
+        },
+        Node::Signal(from) => match to_node {
+            Node::Signal(to) => {
+                to.id = from.id;
+                if changed(from.signal.as_ref(), to.signal.as_ref()) {
+                    vec![NodePatch::SetText {
+                        node_id: from.id,
+                        text: to.signal.render(),
+                    }]
+                } else {
+                    vec![]
+                }
+            }
+            _ => vec![NodePatch::ReplaceChild {
+                node_id: parent_id,
+                child_idx: idx,
+                node: to_node.clone(),
+            }],
+        },
+        // raw HTML is opaque, and replaced whole when it changes
+        Node::RawHtml(from) => match to_node {
+            Node::RawHtml(to) => {
+                to.id = from.id;
+                if from.html != to.html {
+                    vec![NodePatch::Replace {
+                        node_id: from.id,
                         node: to_node.clone(),
                     }]
                 } else {
                     vec![]
                 }
             }
-            _ => vec![],
+            _ => vec![NodePatch::ReplaceChild {
+                node_id: parent_id,
+                child_idx: idx,
+                node: to_node.clone(),
+            }],
+        },
+        Node::Fragment(_) => unreachable!("fragments are diffed as lists"),
+        // child views diff their own renders
+        Node::ViewRef(from) => match to_node {
+            Node::ViewRef(to) if from.order == to.order => vec![],
+            _ => vec![NodePatch::ReplaceChild {
+                node_id: parent_id,
+                child_idx: idx,
+                node: to_node.clone(),
+            }],
         },
-        _ => vec![],
     }
 }
+
+// a signal derived during render is a new signal every time, with the same value
+fn changed(from: &dyn Bindable, to: &dyn Bindable) -> bool {
+    from.id() != to.id() && from.render() != to.render()
+}
+
+fn diff_children(from: Vec<Node>, to: &mut [Node], parent_id: Uuid) -> Vec<NodePatch> {
+    // children are matched by position, so a list that grew or shrank is replaced whole
+    if from.len() != to.len() {
+        return vec![NodePatch::ReplaceChildren {
+            node_id: parent_id,
+            children: to.to_vec(),
+        }];
+    }
+
+    from.into_iter()
+        .zip(to.iter_mut())
+        .enumerate()
+        .flat_map(|(idx, (from, to))| diff(from, to, parent_id, idx))
+        .collect()
+}
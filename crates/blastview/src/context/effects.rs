@@ -1,36 +1,76 @@
 use std::{
     hash::{DefaultHasher, Hash, Hasher},
-    sync::{
-        Arc, Mutex,
-        atomic::{AtomicU64, Ordering},
-    },
+    sync::{Arc, Mutex, Weak},
 };
 
+use crate::context::boundary::{self, Boundary};
+
+type Cleanup = Box<dyn FnOnce() + Send + Sync>;
+
+#[derive(Default)]
 pub struct Effect {
-    hash: AtomicU64,
-    cleanup: Mutex<Option<Box<dyn FnOnce() + Send + Sync>>>,
+    hashes: Mutex<Hashes>,
+    cleanup: Mutex<Option<Cleanup>>,
+}
+
+#[derive(Default)]
+struct Hashes {
+    // the dependencies of the last run, `None` until the effect first runs
+    committed: Option<u64>,
+    // the dependencies of the latest run queued by a render, but not yet committed
+    staged: Option<u64>,
 }
 
 impl Effect {
-    pub fn new<T: Hash>(
-        f: impl (FnOnce() -> Box<dyn FnOnce() + Send + Sync>) + Send + Sync,
-        deps: T,
-    ) -> Self {
-        Self {
-            cleanup: Mutex::new(Some(f())),
-            hash: {
-                let mut hasher = DefaultHasher::new();
-                deps.hash(&mut hasher);
-                AtomicU64::new(hasher.finish())
-            },
+    /// Hashes the dependencies of an effect.
+    pub fn hash_deps<T: Hash>(deps: T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        deps.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Stages a run with new dependencies, unless the effect is already queued with the same ones.
+    ///
+    /// A run staged with other dependencies is superseded, and dependencies changing back to the
+    /// ones of the last run cancel it.
+    pub fn stage(&self, hash: u64) -> bool {
+        let mut hashes = self.hashes.lock().unwrap();
+        if hashes.staged == Some(hash) {
+            return false;
         }
+        if hashes.committed == Some(hash) {
+            hashes.staged = None;
+            return false;
+        }
+        hashes.staged = Some(hash);
+        true
     }
 
-    pub fn run(&self, f: impl (FnOnce() -> Box<dyn FnOnce() + Send + Sync>) + Send + Sync) {
-        let mut cleanup = self.cleanup.lock().unwrap();
-        cleanup.take().unwrap()();
+    fn is_staged(&self, hash: u64) -> bool {
+        self.hashes.lock().unwrap().staged == Some(hash)
+    }
 
-        *cleanup = Some(f());
+    pub fn cleanup(&self) {
+        let cleanup = self.cleanup.lock().unwrap().take();
+        if let Some(cleanup) = cleanup {
+            cleanup();
+        }
+    }
+
+    /// Runs the effect if `hash` is still the latest staged run, and commits it.
+    pub fn run(&self, hash: u64, f: impl FnOnce() -> Cleanup) {
+        {
+            let mut hashes = self.hashes.lock().unwrap();
+            if hashes.staged != Some(hash) {
+                return;
+            }
+            hashes.committed = Some(hash);
+            hashes.staged = None;
+        }
+
+        self.cleanup();
+        let cleanup = f();
+        *self.cleanup.lock().unwrap() = Some(cleanup);
     }
 }
 
@@ -42,35 +82,81 @@ impl Drop for Effect {
     }
 }
 
-#[derive(Default)]
-pub struct EffectRegistry {
-    effects: Mutex<Vec<Arc<Effect>>>,
+/// An effect whose dependencies changed during a render, waiting for the render to be committed.
+///
+/// The new dependencies are only committed once the effect runs, so that an effect discarded
+/// with a failed render is queued again by the next one. A pending effect superseded by a later
+/// render does nothing. A panicking effect or cleanup is reported to the view's error boundary.
+pub struct PendingEffect {
+    effect: Weak<Effect>,
+    hash: u64,
+    body: Box<dyn FnOnce() -> Cleanup + Send>,
+    view: &'static str,
+    boundary: Option<Boundary>,
 }
 
-impl EffectRegistry {
-    pub fn register(&self, effect: Effect) {
-        self.effects.lock().unwrap().push(Arc::new(effect));
+impl PendingEffect {
+    pub fn new(
+        effect: &Arc<Effect>,
+        hash: u64,
+        body: impl FnOnce() -> Cleanup + Send + 'static,
+        view: &'static str,
+        boundary: Option<Boundary>,
+    ) -> Self {
+        Self {
+            effect: Arc::downgrade(effect),
+            hash,
+            body: Box::new(body),
+            view,
+            boundary,
+        }
     }
 
-    pub fn get(&self, idx: usize) -> Option<Arc<Effect>> {
-        self.effects.lock().unwrap().get(idx).cloned()
+    /// Drops the effect along with the render that queued it, so that it is queued again.
+    pub fn discard(self) {
+        if let Some(effect) = self.effect.upgrade() {
+            let mut hashes = effect.hashes.lock().unwrap();
+            if hashes.staged == Some(self.hash) {
+                hashes.staged = None;
+            }
+        }
     }
 
-    /// Returns true if new dependencies differ.
-    pub fn update_deps<T: Hash>(&self, idx: usize, deps: T) -> bool {
-        let mut hasher = DefaultHasher::new();
-        deps.hash(&mut hasher);
-        let hash = hasher.finish();
+    pub fn cleanup(&self) {
+        // a superseded effect does not run, so its previous run is not cleaned up either
+        let effect = self
+            .effect
+            .upgrade()
+            .filter(|effect| effect.is_staged(self.hash));
+        if let Some(effect) = effect {
+            boundary::catch(self.boundary.as_ref(), self.view, || effect.cleanup());
+        }
+    }
 
-        let effect = self.get(idx).unwrap();
+    /// Runs the effect, unless its view has been dropped in the meantime.
+    pub fn run(self) {
+        if let Some(effect) = self.effect.upgrade() {
+            boundary::catch(self.boundary.as_ref(), self.view, || {
+                effect.run(self.hash, self.body)
+            });
+        }
+    }
+}
 
-        let is_changed = hash != effect.hash.load(Ordering::Relaxed);
+#[derive(Default)]
+pub struct EffectRegistry {
+    effects: Mutex<Vec<Arc<Effect>>>,
+}
 
-        if is_changed {
-            effect.hash.store(hash, Ordering::Relaxed);
-        }
+impl EffectRegistry {
+    pub fn register(&self, effect: Effect) -> Arc<Effect> {
+        let effect = Arc::new(effect);
+        self.effects.lock().unwrap().push(Arc::clone(&effect));
+        effect
+    }
 
-        is_changed
+    pub fn get(&self, idx: usize) -> Option<Arc<Effect>> {
+        self.effects.lock().unwrap().get(idx).cloned()
     }
 
     pub fn clear(&self) {
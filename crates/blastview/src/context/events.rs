@@ -1,11 +1,20 @@
-use std::sync::Arc;
+use std::{future::Future, pin::Pin, sync::Arc};
 
 use dashmap::DashMap;
 use uuid::Uuid;
 
+pub type AsyncHandler = Arc<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+#[derive(Clone)]
+pub enum EventHandler {
+    Sync(Arc<dyn Fn() + Send + Sync>),
+    /// Runs as a task owned by the view, marking the element as pending until it completes.
+    Async(AsyncHandler),
+}
+
 #[derive(Default)]
 pub struct EventRegistry {
-    mapping: DashMap<Event, Arc<dyn Fn() + Send + Sync>>,
+    mapping: DashMap<Event, EventHandler>,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -15,14 +24,12 @@ pub struct Event {
 }
 
 impl EventRegistry {
-    pub fn register(&self, event: Event, handler: Arc<dyn Fn() + Send + Sync>) {
+    pub fn register(&self, event: Event, handler: EventHandler) {
         self.mapping.insert(event, handler);
     }
 
-    pub fn handle(&self, event: &Event) {
-        if let Some(handler) = self.mapping.get(event) {
-            handler();
-        }
+    pub fn get(&self, event: &Event) -> Option<EventHandler> {
+        self.mapping.get(event).map(|handler| handler.clone())
     }
 
     pub fn unregister(&self, event: &Event) {
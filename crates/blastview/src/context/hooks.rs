@@ -0,0 +1,143 @@
+use std::{
+    fmt::Display,
+    panic::Location,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    State,
+    Ref,
+    Signal,
+    Effect,
+    View,
+    Boundary,
+    Suspense,
+}
+
+impl Display for HookKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::State => "use_state",
+            Self::Ref => "use_ref",
+            Self::Signal => "use_signal",
+            Self::Effect => "use_effect",
+            Self::View => "create_view",
+            Self::Boundary => "use_error_boundary",
+            Self::Suspense => "use_suspense_boundary",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A hook call, identified by what it stores and where it was made.
+#[derive(Debug, Clone, Copy)]
+pub struct HookSlot {
+    pub kind: HookKind,
+    pub type_name: &'static str,
+    pub location: &'static Location<'static>,
+}
+
+impl HookSlot {
+    #[track_caller]
+    pub fn new<T: ?Sized>(kind: HookKind) -> Self {
+        Self {
+            kind,
+            type_name: std::any::type_name::<T>(),
+            location: Location::caller(),
+        }
+    }
+
+    fn matches(&self, other: &HookSlot) -> bool {
+        self.kind == other.kind && self.type_name == other.type_name
+    }
+}
+
+impl Display for HookSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}::<{}>` at {}",
+            self.kind, self.type_name, self.location
+        )
+    }
+}
+
+/// Records the hooks called by a view, as hooks are matched to their storage purely by call order.
+#[derive(Default)]
+pub struct HookRegistry {
+    slots: Mutex<Vec<HookSlot>>,
+    order: AtomicUsize,
+    // set once a render completes, after which the number of hooks may not change
+    is_sealed: AtomicBool,
+    // set when the current render forgot hooks, leaving the next render free to call others
+    is_open: AtomicBool,
+}
+
+impl HookRegistry {
+    pub fn reset(&self) {
+        self.order.store(0, Ordering::SeqCst);
+    }
+
+    /// Records a hook call, panicking if it differs from the call made at the same position before.
+    pub fn check(&self, view: &str, slot: HookSlot) {
+        let order = self.order.fetch_add(1, Ordering::Relaxed);
+        let mut slots = self.slots.lock().unwrap();
+
+        match slots.get(order) {
+            Some(expected) if expected.matches(&slot) => {}
+            Some(expected) => {
+                let expected = *expected;
+                drop(slots);
+                panic!(
+                    "hook order changed in view `{view}`: hook #{order} is {slot}, \
+                    but was {expected} on previous renders. \
+                    Hooks must be called in the same order on every render, \
+                    never conditionally or in a loop of varying length."
+                );
+            }
+            None if self.is_sealed.load(Ordering::Relaxed) => {
+                drop(slots);
+                panic!(
+                    "view `{view}` called more hooks than on previous renders: \
+                    hook #{order} is {slot}. \
+                    Hooks must be called in the same order on every render, \
+                    never conditionally or in a loop of varying length."
+                );
+            }
+            None => slots.push(slot),
+        }
+    }
+
+    /// Forgets the hooks after the current one, allowing the rest of the render to call different hooks.
+    pub fn forget_rest(&self) {
+        let order = self.order.load(Ordering::Relaxed);
+        self.slots.lock().unwrap().truncate(order);
+        self.is_sealed.store(false, Ordering::Relaxed);
+        self.is_open.store(true, Ordering::Relaxed);
+    }
+
+    /// Marks the end of a successful render, panicking if hooks were skipped.
+    pub fn finish(&self, view: &str) {
+        let called = self.order.load(Ordering::Relaxed);
+        let slots = self.slots.lock().unwrap();
+
+        if called < slots.len() {
+            let missing = slots[called];
+            drop(slots);
+            panic!(
+                "view `{view}` called fewer hooks than on previous renders: \
+                hook #{called} ({missing}) was not called. \
+                Hooks must be called in the same order on every render, \
+                never after an early return."
+            );
+        }
+
+        if !self.is_open.swap(false, Ordering::Relaxed) {
+            self.is_sealed.store(true, Ordering::Relaxed);
+        }
+    }
+}
@@ -5,6 +5,13 @@ macro_rules! use_state {
     };
 }
 
+#[macro_export]
+macro_rules! use_ref {
+    ($cx:expr, $value:expr) => {
+        $cx.use_ref($value)
+    };
+}
+
 #[macro_export]
 macro_rules! use_effect {
     ($cx:expr, $callback:expr $(,)?) => {
@@ -41,40 +48,20 @@ macro_rules! use_memo {
 macro_rules! use_async_memo {
   ($cx:expr, $callback:expr $(,)?) => {
     {
-      let (state, set_state) = $cx.use_state_factory(None);
-      let (is_loading, set_is_loading) = $cx.use_state(false);
-
-      $cx.use_effect(|| {
-        let task = tokio::spawn(async move {
-          set_is_loading(true);
-          let result = $callback().await;
-          tokio::task::yield_now().await;
-          set_state(Some(result));
-          set_is_loading(false);
-        });
-        move || task.abort()
-      }, 0);
+      let resource = $cx.use_resource(0, move || async move {
+        Ok::<_, std::convert::Infallible>($callback().await)
+      });
 
-      (is_loading, state)
+      (resource.is_loading(), resource.value().cloned())
     }
   };
   ($cx:expr, $callback:expr, $( $dep:expr ),+ $(,)?) => {
     {
-      let (state, set_state) = $cx.use_state(None);
-      let (is_loading, set_is_loading) = $cx.use_state(true);
-
-      $cx.use_effect(|| {
-        let task = tokio::spawn(async move {
-          set_is_loading(true);
-          let result = $callback().await;
-          tokio::task::yield_now().await;
-          set_state(Some(result));
-          set_is_loading(false);
-        });
-        move || task.abort()
-      }, &[$( $dep ),+]);
+      let resource = $cx.use_resource(&[$( $dep ),+], move || async move {
+        Ok::<_, std::convert::Infallible>($callback().await)
+      });
 
-      (is_loading, state)
+      (resource.is_loading(), resource.value().cloned())
     }
   };
 }
@@ -1,37 +1,60 @@
 use std::{
+    future::Future,
     hash::Hash,
+    pin::pin,
     sync::{
-        Arc, Mutex,
+        Arc, Mutex, PoisonError,
         atomic::{AtomicBool, AtomicUsize, Ordering},
     },
+    time::Duration,
 };
 
+pub(crate) mod boundary;
 pub(crate) mod diffing;
 pub(crate) mod effects;
 pub mod events;
+mod hooks;
 mod public_api;
+mod refs;
 pub(crate) mod registry;
+mod resource;
+pub(crate) mod signals;
 pub(crate) mod state;
+pub(crate) mod suspense;
+pub(crate) mod tasks;
 pub use public_api::*;
 pub mod context_registry;
 pub mod macros;
 
+use futures::{Stream, StreamExt};
+use tokio::sync::{broadcast, watch};
 use uuid::Uuid;
 
 use crate::{
     context::{
         context_registry::ContextRegistry,
         diffing::diff,
-        effects::{Effect, EffectRegistry},
-        events::{Event, EventRegistry},
+        effects::{Effect, EffectRegistry, PendingEffect},
+        events::{Event, EventHandler, EventRegistry},
+        hooks::{HookKind, HookRegistry, HookSlot},
         registry::OrderedViewRegistry,
-        state::StateRegistry,
+        signals::SignalRegistry,
+        state::{Comparator, Compared, StateRegistry, StateValue},
+        tasks::TaskRegistry,
     },
     node::{ElementNode, Node},
-    rendering::RenderingQueue,
+    rendering::{RenderingQueue, SignalTarget},
+    signal::{Bindable, Signal},
     view::{RenderableView, ViewRef},
 };
+pub use boundary::Boundary;
 pub use diffing::NodePatch;
+pub use refs::Ref;
+pub use resource::{Resource, ResourceState};
+pub use state::Versioned;
+pub use suspense::SuspenseBoundary;
+
+const HOOK_TYPE_CHECKED: &str = "hook slots are checked against the hook order before being read";
 
 pub struct Context {
     pub id: Uuid,
@@ -43,14 +66,30 @@ pub struct Context {
     view_registration_order: AtomicUsize,
 
     event_registry: EventRegistry,
+    signal_registry: SignalRegistry,
+    tasks: Arc<TaskRegistry>,
 
     state_registry: Arc<StateRegistry>,
     state_registration_order: AtomicUsize,
 
     effect_registry: EffectRegistry,
     effect_registration_order: AtomicUsize,
+    pending_effects: Mutex<Vec<PendingEffect>>,
+
+    hooks: HookRegistry,
+
+    // catches the errors of this view, inherited from the nearest ancestor boundary
+    boundary: Option<Boundary>,
+    // registered by `use_error_boundary`, inherited by the views this one creates
+    child_boundary: Mutex<Option<Boundary>>,
+    // tracks the resources of this view, inherited from the nearest ancestor suspense boundary
+    suspense: Option<SuspenseBoundary>,
+    // registered by `use_suspense_boundary`, inherited by the views this one creates
+    child_suspense: Mutex<Option<SuspenseBoundary>>,
 
     has_rendered: AtomicBool,
+    // held for a whole render, so that two renders never match hooks against each other
+    rendering: Mutex<()>,
     last_render: Mutex<Option<Node>>,
     diff: Mutex<Option<Vec<NodePatch>>>,
     view: Arc<dyn RenderableView + Send + Sync>,
@@ -61,6 +100,27 @@ impl Context {
         view: Arc<dyn RenderableView + Send + Sync>,
         context_registry: Arc<ContextRegistry>,
         rendering_queue: Arc<RenderingQueue>,
+    ) -> Arc<Self> {
+        Self::with_boundary(view, context_registry, rendering_queue, None)
+    }
+
+    /// Creates a root context whose errors, and those of descendants not inside another boundary,
+    /// are caught by `boundary`.
+    pub fn with_boundary(
+        view: Arc<dyn RenderableView + Send + Sync>,
+        context_registry: Arc<ContextRegistry>,
+        rendering_queue: Arc<RenderingQueue>,
+        boundary: Option<Boundary>,
+    ) -> Arc<Self> {
+        Self::create(view, context_registry, rendering_queue, boundary, None)
+    }
+
+    fn create(
+        view: Arc<dyn RenderableView + Send + Sync>,
+        context_registry: Arc<ContextRegistry>,
+        rendering_queue: Arc<RenderingQueue>,
+        boundary: Option<Boundary>,
+        suspense: Option<SuspenseBoundary>,
     ) -> Arc<Self> {
         let id = Uuid::new_v4();
 
@@ -74,14 +134,25 @@ impl Context {
             view_registration_order: AtomicUsize::default(),
 
             event_registry: EventRegistry::default(),
+            signal_registry: SignalRegistry::default(),
+            tasks: Arc::new(TaskRegistry::new(view.name(), boundary.clone())),
 
             state_registry: Default::default(),
             state_registration_order: AtomicUsize::default(),
 
             effect_registry: EffectRegistry::default(),
             effect_registration_order: AtomicUsize::default(),
+            pending_effects: Default::default(),
+
+            hooks: HookRegistry::default(),
+
+            boundary,
+            child_boundary: Default::default(),
+            suspense,
+            child_suspense: Default::default(),
 
             has_rendered: AtomicBool::new(false),
+            rendering: Default::default(),
             last_render: Default::default(),
             diff: Default::default(),
             view,
@@ -92,11 +163,13 @@ impl Context {
         cx
     }
 
+    #[track_caller]
     pub(crate) fn create_view<V, F>(&self, factory: F) -> ViewRef
     where
         V: RenderableView + Send + Sync + 'static,
         F: Fn() -> V,
     {
+        self.check_hook(HookSlot::new::<V>(HookKind::View));
         let order = self.view_registration_order.fetch_add(1, Ordering::Relaxed);
 
         if let Some(cx) = self.children.get(order) {
@@ -105,15 +178,29 @@ impl Context {
         }
 
         let view = Arc::new(factory());
-        let context = Context::new(
+        let boundary = self
+            .child_boundary
+            .lock()
+            .unwrap()
+            .clone()
+            .or_else(|| self.boundary.clone());
+        let suspense = self
+            .child_suspense
+            .lock()
+            .unwrap()
+            .clone()
+            .or_else(|| self.suspense.clone());
+        let context = Context::create(
             view,
             Arc::clone(&self.context_registry),
             Arc::clone(&self.rendering_queue),
+            boundary,
+            suspense,
         );
 
         context.force_render();
 
-        self.children.register(context);
+        self.children.register(order, context);
 
         ViewRef { order }
     }
@@ -122,6 +209,7 @@ impl Context {
         self.view_registration_order.store(0, Ordering::SeqCst);
         self.state_registration_order.store(0, Ordering::SeqCst);
         self.effect_registration_order.store(0, Ordering::SeqCst);
+        self.hooks.reset();
         self.state_registry.mark_clean();
     }
 
@@ -133,15 +221,47 @@ impl Context {
         self.force_render()
     }
 
+    /// Renders the view, diffing the result against the previous render.
+    ///
+    /// If rendering fails or panics, the error is reported to the view's error boundary,
+    /// and the previous render is kept.
     pub fn force_render(&self) -> Vec<NodePatch> {
-        let mut last_render = self.last_render.lock().unwrap();
-
+        // the guard holds no data, so a render that panicked leaves nothing to recover
+        let rendering = self
+            .rendering
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
         self.prepare_render();
+
+        let view = self.view.name();
+        let rendered = boundary::catch(self.boundary.as_ref(), view, || {
+            let tree = self.view.render(self)?;
+            self.hooks.finish(view);
+            Ok(tree)
+        });
+        let mut tree = match rendered {
+            Some(Ok(tree)) => tree,
+            Some(Err(error)) => {
+                boundary::report(self.boundary.as_ref(), error);
+                return self.keep_last_render();
+            }
+            None => return self.keep_last_render(),
+        };
+
+        // views created by this render but left out of its tree are unmounted
+        let mut mounted = vec![];
+        mounted_views(&tree, &mut mounted);
+        for cx in self.children.retain(|order| mounted.contains(&order)) {
+            self.context_registry.remove(&cx.id);
+            cx.unmount();
+        }
+
+        let mut last_render = self.last_render.lock().unwrap();
         // for now, atomic node event operations are not possible - diffing is not yet implemented
         self.event_registry.clear();
+        self.signal_registry.clear();
 
-        let mut tree = self.view.render(self);
-        let patches = if self.has_rendered.swap(true, Ordering::Relaxed) {
+        let mut patches = if self.has_rendered.swap(true, Ordering::Relaxed) {
             let diff = diff(last_render.take().unwrap(), &mut tree, self.id, 0);
             *self.diff.lock().unwrap() = Some(diff.clone());
             diff
@@ -153,10 +273,51 @@ impl Context {
             }]
         };
         self.register_events(&tree);
+        self.register_signals(&tree);
         *last_render = Some(tree);
+        drop(last_render);
+
+        // children finish rendering before their parent, so their effects are queued first
+        let effects = std::mem::take(&mut *self.pending_effects.lock().unwrap());
+        self.rendering_queue.enqueue_effects(effects);
+
+        // a view below this boundary failed or started loading while it rendered,
+        // so the fallback is shown right away
+        let child_boundary = self.child_boundary.lock().unwrap().clone();
+        let child_suspense = self.child_suspense.lock().unwrap().clone();
+        let has_caught = child_boundary.is_some_and(|boundary| boundary.take_caught());
+        let has_suspended = child_suspense.is_some_and(|suspense| suspense.take_changed());
+        if has_caught || has_suspended {
+            drop(rendering);
+            patches.extend(self.force_render());
+        }
+
         patches
     }
 
+    fn keep_last_render(&self) -> Vec<NodePatch> {
+        // the effects of a failed render are never committed
+        let effects = std::mem::take(&mut *self.pending_effects.lock().unwrap());
+        for effect in effects {
+            effect.discard();
+        }
+
+        let mut last_render = self.last_render.lock().unwrap();
+        if last_render.is_some() {
+            return vec![];
+        }
+
+        let placeholder: Node = Node::new("bv-error").into();
+        *last_render = Some(placeholder.clone());
+        self.has_rendered.store(true, Ordering::Relaxed);
+
+        vec![NodePatch::ReplaceViewChildren {
+            view_id: self.id,
+            children: vec![placeholder],
+        }]
+    }
+
+    #[track_caller]
     pub(crate) fn use_state<T: Send + Sync + PartialEq + Clone + 'static>(
         &self,
         initial_value: T,
@@ -164,6 +325,7 @@ impl Context {
         self.use_state_factory(|| initial_value)
     }
 
+    #[track_caller]
     pub(crate) fn use_state_factory<
         T: Send + Sync + PartialEq + Clone + 'static,
         F: FnOnce() -> T,
@@ -171,49 +333,373 @@ impl Context {
         &self,
         factory: F,
     ) -> (T, Arc<dyn Fn(T) + Send + Sync>) {
+        self.register_state(factory, |value| value)
+    }
+
+    #[track_caller]
+    pub(crate) fn use_state_with<T, E>(
+        &self,
+        initial_value: T,
+        eq: E,
+    ) -> (T, Arc<dyn Fn(T) + Send + Sync>)
+    where
+        T: Send + Sync + Clone + 'static,
+        E: Fn(&T, &T) -> bool + Send + Sync + 'static,
+    {
+        let eq: Comparator<T> = Arc::new(eq);
+        self.register_state(
+            || initial_value,
+            move |value| Compared::new(value, Arc::clone(&eq)),
+        )
+    }
+
+    #[track_caller]
+    pub(crate) fn use_state_always<T: Send + Sync + Clone + 'static>(
+        &self,
+        initial_value: T,
+    ) -> (T, Arc<dyn Fn(T) + Send + Sync>) {
+        self.use_state_with(initial_value, |_, _| false)
+    }
+
+    #[track_caller]
+    fn register_state<T, S>(
+        &self,
+        factory: impl FnOnce() -> T,
+        wrap: impl Fn(T) -> S + Send + Sync + 'static,
+    ) -> (T, Arc<dyn Fn(T) + Send + Sync>)
+    where
+        T: Send + Sync + Clone + 'static,
+        S: StateValue + 'static,
+    {
+        self.check_hook(HookSlot::new::<T>(HookKind::State));
         let order = self
             .state_registration_order
             .fetch_add(1, Ordering::Relaxed);
 
+        let wrap = Arc::new(wrap);
         let state_registry = Arc::clone(&self.state_registry);
         let rendering_queue = Arc::clone(&self.rendering_queue);
         let id = self.id;
-        let update: Arc<dyn Fn(T) + Send + Sync> = Arc::new(move |value| {
-            if state_registry.update(order, value) {
-                rendering_queue.enqueue(id);
-            }
-        });
+        let update: Arc<dyn Fn(T) + Send + Sync> = {
+            let wrap = Arc::clone(&wrap);
+            Arc::new(move |value| {
+                if state_registry.update(order, wrap(value)) {
+                    rendering_queue.enqueue(id);
+                }
+            })
+        };
 
-        if let Some(state) = self.state_registry.get::<T>(order) {
-            return (state.as_any().downcast_ref::<T>().unwrap().clone(), update);
+        if let Some(state) = self.state_registry.get(order) {
+            let state = state.as_any().downcast_ref::<T>().expect(HOOK_TYPE_CHECKED);
+            return (state.clone(), update);
         }
 
         let initial_value = factory();
 
-        self.state_registry.register(initial_value.clone());
+        self.state_registry.register(wrap(initial_value.clone()));
 
         (initial_value, update)
     }
 
+    #[track_caller]
+    pub(crate) fn use_ref<T: Send + 'static>(&self, initial_value: T) -> Ref<T> {
+        self.use_slot(HookKind::Ref, || Ref::new(initial_value))
+    }
+
+    #[track_caller]
+    pub(crate) fn use_signal<T: Send + Sync + 'static>(&self, initial_value: T) -> Signal<T> {
+        self.use_slot(HookKind::Signal, || Signal::new(initial_value))
+    }
+
+    /// Registers a state slot that is never updated, returning the same value on every render.
+    #[track_caller]
+    fn use_slot<S: StateValue + Clone + 'static>(
+        &self,
+        kind: HookKind,
+        factory: impl FnOnce() -> S,
+    ) -> S {
+        self.check_hook(HookSlot::new::<S>(kind));
+        let order = self
+            .state_registration_order
+            .fetch_add(1, Ordering::Relaxed);
+
+        if let Some(value) = self.state_registry.get(order) {
+            return value
+                .as_any()
+                .downcast_ref::<S>()
+                .expect(HOOK_TYPE_CHECKED)
+                .clone();
+        }
+
+        let value = factory();
+
+        self.state_registry.register(value.clone());
+
+        value
+    }
+
+    #[track_caller]
     pub(crate) fn use_effect<F, T, C>(&self, f: F, deps: T)
     where
-        F: (FnOnce() -> C) + Send + Sync,
+        F: (FnOnce() -> C) + Send + 'static,
         T: Hash,
         C: FnOnce() + Send + Sync + 'static,
     {
+        self.check_hook(HookSlot::new::<T>(HookKind::Effect));
         let order = self
             .effect_registration_order
             .fetch_add(1, Ordering::Relaxed);
 
-        if let Some(effect) = self.effect_registry.get(order) {
-            if self.effect_registry.update_deps(order, &deps) {
-                effect.run(move || Box::new(f()));
-            }
+        let effect = match self.effect_registry.get(order) {
+            Some(effect) => effect,
+            None => self.effect_registry.register(Effect::default()),
+        };
+        let hash = Effect::hash_deps(&deps);
+        if !effect.stage(hash) {
             return;
         }
 
-        let effect = Effect::new(move || Box::new(f()), deps);
-        self.effect_registry.register(effect);
+        // effects run once the render is committed, see `RenderingQueue::flush_effects`
+        self.pending_effects
+            .lock()
+            .unwrap()
+            .push(PendingEffect::new(
+                &effect,
+                hash,
+                move || Box::new(f()),
+                self.view.name(),
+                self.boundary.clone(),
+            ));
+    }
+
+    #[track_caller]
+    pub(crate) fn use_error_boundary(&self) -> Boundary {
+        let boundary = self.use_slot(HookKind::Boundary, || {
+            Boundary::new(self.id, Arc::clone(&self.rendering_queue))
+        });
+        *self.child_boundary.lock().unwrap() = Some(boundary.clone());
+
+        if boundary.error().is_some() {
+            self.discard_children();
+        }
+
+        boundary
+    }
+
+    #[track_caller]
+    pub(crate) fn use_suspense_boundary(&self) -> SuspenseBoundary {
+        let suspense = self.use_slot(HookKind::Suspense, || {
+            SuspenseBoundary::new(self.id, Arc::clone(&self.rendering_queue))
+        });
+        *self.child_suspense.lock().unwrap() = Some(suspense.clone());
+        suspense
+    }
+
+    /// Drops every child view, along with the hooks called after the current one.
+    fn discard_children(&self) {
+        for cx in self.children.clear() {
+            self.context_registry.remove(&cx.id);
+            cx.unmount();
+        }
+        self.hooks.forget_rest();
+    }
+
+    /// Aborts the tasks of a view removed from its parent, and drops its descendants.
+    ///
+    /// Tasks are aborted right away rather than when the context is dropped, since a task may
+    /// itself hold on to the context.
+    fn unmount(&self) {
+        self.tasks.abort_all();
+        for cx in self.children.clear() {
+            self.context_registry.remove(&cx.id);
+            cx.unmount();
+        }
+    }
+
+    #[track_caller]
+    pub(crate) fn use_resource<D, F, Fut, T, E>(&self, deps: D, fetcher: F) -> Resource<T, E>
+    where
+        D: Hash,
+        F: FnOnce() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Send + Sync + Clone + 'static,
+        E: Send + Sync + Clone + 'static,
+    {
+        // settled states are always considered changed, as `T` and `E` need not be comparable
+        let (state, set_state) = self.use_state_with(ResourceState::Loading, |prev, next| {
+            matches!(
+                (prev, next),
+                (ResourceState::Loading, ResourceState::Loading)
+            )
+        });
+        let fetches = self.use_ref(0u64);
+        let (generation, set_generation) = self.use_state(0u64);
+
+        let key = self.use_ref(Uuid::new_v4()).get();
+        let resources = &self.rendering_queue.resources;
+        if matches!(state, ResourceState::Loading) {
+            resources.suspend(key, self.id);
+            if let Some(suspense) = &self.suspense {
+                suspense.suspend(key, self.id);
+            }
+        } else {
+            resources.resume(key);
+            if let Some(suspense) = &self.suspense {
+                suspense.resume(key);
+            }
+        }
+
+        let tasks = Arc::clone(&self.tasks);
+        self.use_effect(
+            move || {
+                set_state(ResourceState::Loading);
+                // the previous fetch is aborted by the effect cleanup, so a stale result is never set
+                let task = tasks.spawn(async move {
+                    let state = match fetcher().await {
+                        Ok(value) => ResourceState::Ready(value),
+                        Err(error) => ResourceState::Error(error),
+                    };
+                    set_state(state);
+                });
+                move || task.abort()
+            },
+            (deps, generation),
+        );
+
+        Resource {
+            state,
+            refetch: Arc::new(move || {
+                let generation = {
+                    let mut fetches = fetches.lock();
+                    *fetches += 1;
+                    *fetches
+                };
+                set_generation(generation);
+            }),
+        }
+    }
+
+    #[track_caller]
+    pub(crate) fn use_stream<S, F>(&self, factory: F) -> Option<S::Item>
+    where
+        F: FnOnce() -> S + Send + Sync + 'static,
+        S: Stream + Send + 'static,
+        S::Item: Send + Sync + Clone + 'static,
+    {
+        let (latest, set_latest) = self.use_state_always(None);
+
+        let tasks = Arc::clone(&self.tasks);
+        self.use_effect(
+            move || {
+                let task = tasks.spawn(async move {
+                    let mut stream = pin!(factory());
+                    while let Some(item) = stream.next().await {
+                        set_latest(Some(item));
+                    }
+                });
+                move || task.abort()
+            },
+            (),
+        );
+
+        latest
+    }
+
+    #[track_caller]
+    pub(crate) fn use_broadcast<T>(&self, receiver: broadcast::Receiver<T>) -> Option<T>
+    where
+        T: Send + Sync + Clone + 'static,
+    {
+        self.use_stream(move || {
+            futures::stream::unfold(receiver, async |mut receiver| {
+                loop {
+                    match receiver.recv().await {
+                        Ok(item) => return Some((item, receiver)),
+                        // only the latest item is kept, so missed ones don't matter
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            })
+        })
+    }
+
+    #[track_caller]
+    pub(crate) fn use_watch<T>(&self, receiver: watch::Receiver<T>) -> T
+    where
+        T: Send + Sync + Clone + 'static,
+    {
+        let initial_value = receiver.borrow().clone();
+        let latest = self.use_stream(move || {
+            futures::stream::unfold(receiver, async |mut receiver| {
+                receiver.changed().await.ok()?;
+                let value = receiver.borrow_and_update().clone();
+                Some((value, receiver))
+            })
+        });
+
+        latest.unwrap_or(initial_value)
+    }
+
+    #[track_caller]
+    pub(crate) fn use_interval<F>(&self, period: Duration, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback = self.use_latest(callback);
+        let tasks = Arc::clone(&self.tasks);
+        self.use_effect(
+            move || {
+                let task = tasks.spawn(async move {
+                    let mut interval = tokio::time::interval(period);
+                    // the first tick completes immediately
+                    interval.tick().await;
+                    loop {
+                        interval.tick().await;
+                        (callback.get())();
+                    }
+                });
+                move || task.abort()
+            },
+            period,
+        );
+    }
+
+    #[track_caller]
+    pub(crate) fn use_timeout<F>(&self, delay: Duration, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback = self.use_latest(callback);
+        let tasks = Arc::clone(&self.tasks);
+        self.use_effect(
+            move || {
+                let task = tasks.spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    (callback.get())();
+                });
+                move || task.abort()
+            },
+            delay,
+        );
+    }
+
+    /// Keeps the callback from the most recent render, so that timers don't call stale closures.
+    #[track_caller]
+    fn use_latest<F>(&self, callback: F) -> Ref<Arc<dyn Fn() + Send + Sync>>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback: Arc<dyn Fn() + Send + Sync> = Arc::new(callback);
+        let latest = self.use_ref(Arc::clone(&callback));
+        latest.set(callback);
+        latest
+    }
+
+    /// Panics with a description of both calls if hooks were called in a different order than before.
+    #[track_caller]
+    fn check_hook(&self, slot: HookSlot) {
+        self.hooks.check(self.view.name(), slot);
     }
 
     fn register_events(&self, node: &Node) {
@@ -225,22 +711,75 @@ impl Context {
                         event: event.to_string(),
                     };
                     tracing::debug!("[{}] event registered: {event:?}", node.tag);
-                    self.event_registry
-                        .register(event.clone(), Arc::clone(handler));
+                    self.event_registry.register(event.clone(), handler.clone());
                 }
 
                 for child in node.children.iter() {
                     self.register_events(child);
                 }
             }
+            Node::Fragment(fragment) => {
+                for child in fragment.0.iter() {
+                    self.register_events(child);
+                }
+            }
             // ignore text elements
             // child views have already registered their own events
             _ => {}
         }
     }
 
+    fn register_signals(&self, node: &Node) {
+        match node {
+            Node::Element(node) => {
+                for (attr, signal) in node.bindings.iter() {
+                    let target = SignalTarget {
+                        node_id: node.id,
+                        attr: Some(attr.to_string()),
+                    };
+                    self.bind_signal(target, signal);
+                }
+
+                for child in node.children.iter() {
+                    self.register_signals(child);
+                }
+            }
+            Node::Fragment(fragment) => {
+                for child in fragment.0.iter() {
+                    self.register_signals(child);
+                }
+            }
+            Node::Signal(node) => {
+                let target = SignalTarget {
+                    node_id: node.id,
+                    attr: None,
+                };
+                self.bind_signal(target, &node.signal);
+            }
+            _ => {}
+        }
+    }
+
+    fn bind_signal(&self, target: SignalTarget, signal: &Arc<dyn Bindable>) {
+        let rendering_queue = Arc::clone(&self.rendering_queue);
+        let changed = Arc::clone(signal);
+        self.signal_registry.register(
+            signal.as_ref(),
+            Arc::new(move || rendering_queue.enqueue_signal(target.clone(), Arc::clone(&changed))),
+        );
+    }
+
     pub fn dispatch_event(&self, event: &Event) {
-        self.event_registry.handle(event);
+        match self.event_registry.get(event) {
+            Some(EventHandler::Sync(handler)) => {
+                boundary::catch(self.boundary.as_ref(), self.view.name(), || handler());
+            }
+            Some(EventHandler::Async(handler)) => {
+                self.tasks
+                    .spawn_pending(event.node_id, &self.rendering_queue, handler());
+            }
+            None => {}
+        }
 
         self.children.each(|cx| cx.dispatch_event(&event));
     }
@@ -250,7 +789,10 @@ impl Context {
     }
 
     pub fn view_node(&self) -> ElementNode {
-        Node::new("bv-view").attr("data-view", &self.id.to_string())
+        // the view node shares the context's id, so that patches can target the view's root
+        let mut node = Node::new("bv-view").attr("data-view", &self.id.to_string());
+        node.id = self.id;
+        node
     }
 
     pub fn with_last_render<R>(&self, f: impl FnOnce(Option<&Node>) -> R) -> R {
@@ -258,10 +800,34 @@ impl Context {
     }
 }
 
+/// Collects the order of the child views placed in a rendered tree.
+fn mounted_views(node: &Node, orders: &mut Vec<usize>) {
+    match node {
+        Node::Element(node) => {
+            for child in node.children.iter() {
+                mounted_views(child, orders);
+            }
+        }
+        Node::Fragment(fragment) => {
+            for child in fragment.0.iter() {
+                mounted_views(child, orders);
+            }
+        }
+        Node::ViewRef(view) => orders.push(view.order),
+        _ => {}
+    }
+}
+
 impl Drop for Context {
     fn drop(&mut self) {
+        self.rendering_queue.resources.release(self.id);
+        if let Some(suspense) = &self.suspense {
+            suspense.release(self.id);
+        }
+        self.tasks.abort_all();
         self.effect_registry.clear();
         self.event_registry.clear();
+        self.signal_registry.clear();
         self.state_registry.clear();
     }
 }
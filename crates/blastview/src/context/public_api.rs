@@ -1,10 +1,21 @@
+use std::future::Future;
 use std::hash::Hash;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::context::Context;
+use futures::Stream;
+use tokio::sync::{broadcast, watch};
+
+use crate::context::{Boundary, Context, Ref, Resource, SuspenseBoundary};
+use crate::signal::Signal;
 use crate::view::{RenderableView, ViewRef};
 
 pub trait ViewContext {
+    /// Creates a child view on the first render, and re-renders it on the next ones.
+    ///
+    /// The returned view must be placed in this render's tree: a view left out of it is dropped,
+    /// with its state and tasks, and created again on the next render. To keep a view alive while
+    /// it is not shown, place it inside an element hidden with the `hidden` attribute.
     fn create_view<V, F>(&self, factory: F) -> ViewRef
     where
         V: RenderableView + Send + Sync + 'static,
@@ -20,14 +31,89 @@ pub trait ViewContext {
         factory: F,
     ) -> (T, Arc<dyn Fn(T) + Send + Sync>);
 
+    fn use_state_with<T, E>(&self, initial_value: T, eq: E) -> (T, Arc<dyn Fn(T) + Send + Sync>)
+    where
+        T: Send + Sync + Clone + 'static,
+        E: Fn(&T, &T) -> bool + Send + Sync + 'static;
+
+    fn use_state_always<T: Send + Sync + Clone + 'static>(
+        &self,
+        initial_value: T,
+    ) -> (T, Arc<dyn Fn(T) + Send + Sync>);
+
+    fn use_ref<T: Send + 'static>(&self, initial_value: T) -> Ref<T>;
+
+    fn use_signal<T: Send + Sync + 'static>(&self, initial_value: T) -> Signal<T>;
+
+    /// Runs `f` after the render is committed whenever `deps` change.
+    ///
+    /// The cleanup returned by `f` runs before the next run, or when the view is dropped.
     fn use_effect<F, T, C>(&self, f: F, deps: T)
     where
-        F: (FnOnce() -> C) + Send + Sync,
+        F: (FnOnce() -> C) + Send + 'static,
         T: Hash,
         C: FnOnce() + Send + Sync + 'static;
+
+    /// Catches the errors of the views created by this view, and of their descendants.
+    ///
+    /// While an error is caught, the views created by this view are dropped until the boundary is reset.
+    /// It must be called before this view creates any other view.
+    fn use_error_boundary(&self) -> Boundary;
+
+    /// Tracks the resources loading in the views created by this view, and in their descendants.
+    fn use_suspense_boundary(&self) -> SuspenseBoundary;
+
+    /// Runs `fetcher` in a task owned by the view whenever `deps` change.
+    ///
+    /// A fetch still in progress when `deps` change, or when the view is dropped, is cancelled.
+    fn use_resource<D, F, Fut, T, E>(&self, deps: D, fetcher: F) -> Resource<T, E>
+    where
+        D: Hash,
+        F: FnOnce() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Send + Sync + Clone + 'static,
+        E: Send + Sync + Clone + 'static;
+
+    /// Subscribes the view to a stream for its whole lifetime, returning the latest item.
+    ///
+    /// `factory` is only called on the first render.
+    fn use_stream<S, F>(&self, factory: F) -> Option<S::Item>
+    where
+        F: FnOnce() -> S + Send + Sync + 'static,
+        S: Stream + Send + 'static,
+        S::Item: Send + Sync + Clone + 'static;
+
+    /// Returns the latest message received on a broadcast channel.
+    ///
+    /// Only the receiver passed on the first render is used.
+    fn use_broadcast<T>(&self, receiver: broadcast::Receiver<T>) -> Option<T>
+    where
+        T: Send + Sync + Clone + 'static;
+
+    /// Returns the current value of a watch channel.
+    ///
+    /// Only the receiver passed on the first render is used.
+    fn use_watch<T>(&self, receiver: watch::Receiver<T>) -> T
+    where
+        T: Send + Sync + Clone + 'static;
+
+    /// Calls `callback` every `period`, restarting the timer when `period` changes.
+    ///
+    /// The timer is stopped when the view is dropped.
+    fn use_interval<F>(&self, period: Duration, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static;
+
+    /// Calls `callback` once after `delay`, restarting the timer when `delay` changes.
+    ///
+    /// The timer is stopped when the view is dropped.
+    fn use_timeout<F>(&self, delay: Duration, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static;
 }
 
 impl ViewContext for Context {
+    #[track_caller]
     fn create_view<V, F>(&self, factory: F) -> ViewRef
     where
         V: RenderableView + Send + Sync + 'static,
@@ -36,6 +122,7 @@ impl ViewContext for Context {
         self.create_view(factory)
     }
 
+    #[track_caller]
     fn use_state<T: Send + Sync + PartialEq + Clone + 'static>(
         &self,
         initial_value: T,
@@ -43,15 +130,108 @@ impl ViewContext for Context {
         self.use_state(initial_value)
     }
 
+    #[track_caller]
+    fn use_state_with<T, E>(&self, initial_value: T, eq: E) -> (T, Arc<dyn Fn(T) + Send + Sync>)
+    where
+        T: Send + Sync + Clone + 'static,
+        E: Fn(&T, &T) -> bool + Send + Sync + 'static,
+    {
+        self.use_state_with(initial_value, eq)
+    }
+
+    #[track_caller]
+    fn use_state_always<T: Send + Sync + Clone + 'static>(
+        &self,
+        initial_value: T,
+    ) -> (T, Arc<dyn Fn(T) + Send + Sync>) {
+        self.use_state_always(initial_value)
+    }
+
+    #[track_caller]
+    fn use_ref<T: Send + 'static>(&self, initial_value: T) -> Ref<T> {
+        self.use_ref(initial_value)
+    }
+
+    #[track_caller]
+    fn use_error_boundary(&self) -> Boundary {
+        self.use_error_boundary()
+    }
+
+    #[track_caller]
+    fn use_suspense_boundary(&self) -> SuspenseBoundary {
+        self.use_suspense_boundary()
+    }
+
+    #[track_caller]
+    fn use_signal<T: Send + Sync + 'static>(&self, initial_value: T) -> Signal<T> {
+        self.use_signal(initial_value)
+    }
+
+    #[track_caller]
     fn use_effect<F, T, C>(&self, f: F, deps: T)
     where
-        F: (FnOnce() -> C) + Send + Sync,
+        F: (FnOnce() -> C) + Send + 'static,
         T: Hash,
         C: FnOnce() + Send + Sync + 'static,
     {
         self.use_effect(f, deps);
     }
 
+    #[track_caller]
+    fn use_resource<D, F, Fut, T, E>(&self, deps: D, fetcher: F) -> Resource<T, E>
+    where
+        D: Hash,
+        F: FnOnce() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Send + Sync + Clone + 'static,
+        E: Send + Sync + Clone + 'static,
+    {
+        self.use_resource(deps, fetcher)
+    }
+
+    #[track_caller]
+    fn use_stream<S, F>(&self, factory: F) -> Option<S::Item>
+    where
+        F: FnOnce() -> S + Send + Sync + 'static,
+        S: Stream + Send + 'static,
+        S::Item: Send + Sync + Clone + 'static,
+    {
+        self.use_stream(factory)
+    }
+
+    #[track_caller]
+    fn use_broadcast<T>(&self, receiver: broadcast::Receiver<T>) -> Option<T>
+    where
+        T: Send + Sync + Clone + 'static,
+    {
+        self.use_broadcast(receiver)
+    }
+
+    #[track_caller]
+    fn use_watch<T>(&self, receiver: watch::Receiver<T>) -> T
+    where
+        T: Send + Sync + Clone + 'static,
+    {
+        self.use_watch(receiver)
+    }
+
+    #[track_caller]
+    fn use_interval<F>(&self, period: Duration, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.use_interval(period, callback);
+    }
+
+    #[track_caller]
+    fn use_timeout<F>(&self, delay: Duration, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.use_timeout(delay, callback);
+    }
+
+    #[track_caller]
     fn use_state_factory<T: Send + Sync + PartialEq + Clone + 'static, F: FnOnce() -> T>(
         &self,
         factory: F,
@@ -0,0 +1,45 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A mutable cell that persists across renders of a view.
+///
+/// Writing to a `Ref` never enqueues a re-render.
+pub struct Ref<T> {
+    value: Arc<Mutex<T>>,
+}
+
+impl<T> Ref<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value: Arc::new(Mutex::new(value)),
+        }
+    }
+
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        self.value.lock().unwrap()
+    }
+
+    pub fn set(&self, value: T) -> T {
+        std::mem::replace(&mut *self.lock(), value)
+    }
+}
+
+impl<T: Clone> Ref<T> {
+    pub fn get(&self) -> T {
+        self.lock().clone()
+    }
+}
+
+impl<T> Clone for Ref<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Arc::clone(&self.value),
+        }
+    }
+}
+
+/// Two refs are equal if they point to the same cell.
+impl<T> PartialEq for Ref<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.value, &other.value)
+    }
+}
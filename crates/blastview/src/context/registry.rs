@@ -2,24 +2,56 @@ use std::sync::{Arc, Mutex};
 
 use crate::context::Context;
 
+/// Child views by the order in which they were created, with gaps for unmounted views.
 #[derive(Default)]
 pub struct OrderedViewRegistry {
-    views: Mutex<Vec<Arc<Context>>>,
+    views: Mutex<Vec<Option<Arc<Context>>>>,
 }
 
 impl OrderedViewRegistry {
-    pub fn register(&self, cx: Arc<Context>) {
-        self.views.lock().unwrap().push(cx);
+    pub fn register(&self, order: usize, cx: Arc<Context>) {
+        let mut views = self.views.lock().unwrap();
+        if views.len() <= order {
+            views.resize(order + 1, None);
+        }
+        views[order] = Some(cx);
     }
 
     pub fn get(&self, order: usize) -> Option<Arc<Context>> {
-        self.views.lock().unwrap().get(order).cloned()
+        self.views.lock().unwrap().get(order).cloned().flatten()
+    }
+
+    pub fn clear(&self) -> Vec<Arc<Context>> {
+        std::mem::take(&mut *self.views.lock().unwrap())
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Removes the views whose order is not kept, and returns them.
+    pub fn retain<F>(&self, keep: F) -> Vec<Arc<Context>>
+    where
+        F: Fn(usize) -> bool,
+    {
+        let mut views = self.views.lock().unwrap();
+        let mut removed = vec![];
+        for (order, view) in views.iter_mut().enumerate() {
+            if !keep(order) {
+                removed.extend(view.take());
+            }
+        }
+        removed
     }
 
     pub fn each<F>(&self, f: F)
     where
         F: Fn(&Context),
     {
-        self.views.lock().unwrap().iter().for_each(|cx| f(&cx));
+        self.views
+            .lock()
+            .unwrap()
+            .iter()
+            .flatten()
+            .for_each(|cx| f(cx));
     }
 }
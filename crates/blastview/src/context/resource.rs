@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub enum ResourceState<T, E> {
+    Loading,
+    Ready(T),
+    Error(E),
+}
+
+/// The result of an asynchronous fetch, see [`ViewContext::use_resource`](crate::context::ViewContext::use_resource).
+#[derive(Clone)]
+pub struct Resource<T, E> {
+    pub(crate) state: ResourceState<T, E>,
+    pub(crate) refetch: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl<T, E> Resource<T, E> {
+    pub fn state(&self) -> &ResourceState<T, E> {
+        &self.state
+    }
+
+    pub fn is_loading(&self) -> bool {
+        matches!(self.state, ResourceState::Loading)
+    }
+
+    pub fn value(&self) -> Option<&T> {
+        match &self.state {
+            ResourceState::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&E> {
+        match &self.state {
+            ResourceState::Error(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Runs the fetch again, cancelling the one in progress.
+    pub fn refetch(&self) {
+        (self.refetch)()
+    }
+}
@@ -0,0 +1,20 @@
+use std::sync::Mutex;
+
+use crate::signal::{Bindable, Listener};
+
+#[derive(Default)]
+pub struct SignalRegistry {
+    listeners: Mutex<Vec<Listener>>,
+}
+
+impl SignalRegistry {
+    /// Subscribes `listener` to `signal` until the registry is cleared.
+    pub fn register(&self, signal: &dyn Bindable, listener: Listener) {
+        signal.subscribe(&listener);
+        self.listeners.lock().unwrap().push(listener);
+    }
+
+    pub fn clear(&self) {
+        self.listeners.lock().unwrap().clear();
+    }
+}
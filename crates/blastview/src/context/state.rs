@@ -1,5 +1,6 @@
 use std::{
     any::Any,
+    ops::Deref,
     sync::{Arc, Mutex, atomic::AtomicBool},
 };
 
@@ -22,6 +23,78 @@ impl<T: Send + Sync + PartialEq + 'static> StateValue for T {
     }
 }
 
+pub type Comparator<T> = Arc<dyn Fn(&T, &T) -> bool + Send + Sync>;
+
+/// State compared with a custom function instead of `PartialEq`.
+pub struct Compared<T> {
+    value: T,
+    eq: Comparator<T>,
+}
+
+impl<T> Compared<T> {
+    pub fn new(value: T, eq: Comparator<T>) -> Self {
+        Self { value, eq }
+    }
+}
+
+impl<T: Send + Sync + 'static> StateValue for Compared<T> {
+    fn as_any(&self) -> &dyn Any {
+        &self.value
+    }
+
+    fn eq(&self, other: &dyn StateValue) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<T>()
+            .is_some_and(|other| (self.eq)(&self.value, other))
+    }
+}
+
+/// A value that is compared by its version rather than by its contents.
+///
+/// Useful for state that is expensive to compare or has no `PartialEq`:
+/// bump the version with [`Versioned::next`] to trigger a re-render.
+#[derive(Debug, Clone)]
+pub struct Versioned<T> {
+    value: T,
+    version: u64,
+}
+
+impl<T> Versioned<T> {
+    pub fn new(value: T) -> Self {
+        Self { value, version: 0 }
+    }
+
+    pub fn next(&self, value: T) -> Self {
+        Self {
+            value,
+            version: self.version.wrapping_add(1),
+        }
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for Versioned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> PartialEq for Versioned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version
+    }
+}
+
 #[derive(Default)]
 pub struct StateRegistry {
     state: Mutex<Vec<Arc<dyn StateValue>>>,
@@ -35,14 +108,11 @@ impl StateRegistry {
         value
     }
 
-    pub fn get<T: Send + Sync + PartialEq + 'static>(
-        &self,
-        idx: usize,
-    ) -> Option<Arc<dyn StateValue>> {
+    pub fn get(&self, idx: usize) -> Option<Arc<dyn StateValue>> {
         self.state.lock().unwrap().get(idx).cloned()
     }
 
-    pub fn update<T: Send + Sync + PartialEq + 'static>(&self, idx: usize, value: T) -> bool {
+    pub fn update<T: StateValue + 'static>(&self, idx: usize, value: T) -> bool {
         let mut state = self.state.lock().unwrap();
         let prev_value = state.get_mut(idx).unwrap();
         if prev_value.eq(&value) {
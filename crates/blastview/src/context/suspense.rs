@@ -0,0 +1,114 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use uuid::Uuid;
+
+use crate::rendering::RenderingQueue;
+
+/// Tracks the resources still loading below the view that registered it.
+///
+/// See [`crate::view::Suspense`].
+#[derive(Clone)]
+pub struct SuspenseBoundary {
+    inner: Arc<SuspenseInner>,
+}
+
+struct SuspenseInner {
+    view_id: Uuid,
+    rendering_queue: Arc<RenderingQueue>,
+    pending: PendingResources,
+    // set when the boundary becomes pending or settles, until the boundary view re-renders
+    has_changed: AtomicBool,
+}
+
+impl SuspenseBoundary {
+    pub(crate) fn new(view_id: Uuid, rendering_queue: Arc<RenderingQueue>) -> Self {
+        Self {
+            inner: Arc::new(SuspenseInner {
+                view_id,
+                rendering_queue,
+                pending: Default::default(),
+                has_changed: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns true while any resource below the boundary is loading.
+    pub fn is_pending(&self) -> bool {
+        !self.inner.pending.is_empty()
+    }
+
+    pub(crate) fn suspend(&self, resource: Uuid, view_id: Uuid) {
+        self.notify_if(self.inner.pending.suspend(resource, view_id));
+    }
+
+    pub(crate) fn resume(&self, resource: Uuid) {
+        self.notify_if(self.inner.pending.resume(resource));
+    }
+
+    /// Forgets the resources of a dropped view.
+    pub(crate) fn release(&self, view_id: Uuid) {
+        self.notify_if(self.inner.pending.release(view_id));
+    }
+
+    fn notify_if(&self, has_changed: bool) {
+        if has_changed {
+            self.inner.has_changed.store(true, Ordering::Relaxed);
+            self.inner.rendering_queue.enqueue(self.inner.view_id);
+        }
+    }
+
+    /// Returns true if the boundary became pending or settled since the last call.
+    pub(crate) fn take_changed(&self) -> bool {
+        self.inner.has_changed.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// Two boundaries are equal if they were registered by the same hook.
+impl PartialEq for SuspenseBoundary {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+/// Resources that are loading, mapped to the view that owns them.
+///
+/// Every update returns true if the set became empty, or stopped being empty.
+#[derive(Default)]
+pub(crate) struct PendingResources {
+    pending: Mutex<HashMap<Uuid, Uuid>>,
+}
+
+impl PendingResources {
+    pub fn is_empty(&self) -> bool {
+        self.pending.lock().unwrap().is_empty()
+    }
+
+    pub fn suspend(&self, resource: Uuid, view_id: Uuid) -> bool {
+        self.update(|pending| {
+            pending.insert(resource, view_id);
+        })
+    }
+
+    pub fn resume(&self, resource: Uuid) -> bool {
+        self.update(|pending| {
+            pending.remove(&resource);
+        })
+    }
+
+    pub fn release(&self, view_id: Uuid) -> bool {
+        self.update(|pending| pending.retain(|_, owner| *owner != view_id))
+    }
+
+    fn update(&self, f: impl FnOnce(&mut HashMap<Uuid, Uuid>)) -> bool {
+        let mut pending = self.pending.lock().unwrap();
+        let was_empty = pending.is_empty();
+        f(&mut pending);
+        was_empty != pending.is_empty()
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    collections::HashMap,
+    panic::AssertUnwindSafe,
+    sync::{Arc, Mutex},
+};
+
+use futures::FutureExt;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use crate::{
+    context::{
+        NodePatch,
+        boundary::{self, Boundary},
+    },
+    rendering::RenderingQueue,
+    view::ViewError,
+};
+
+pub const PENDING_ATTR: &str = "data-pending";
+
+/// Tasks spawned on behalf of a view, aborted when the view is unmounted or dropped.
+///
+/// A task that panics is reported to the view's error boundary.
+pub struct TaskRegistry {
+    view: &'static str,
+    boundary: Option<Boundary>,
+    handles: Mutex<Vec<AbortHandle>>,
+    pending: Mutex<HashMap<Uuid, usize>>,
+}
+
+impl TaskRegistry {
+    pub fn new(view: &'static str, boundary: Option<Boundary>) -> Self {
+        Self {
+            view,
+            boundary,
+            handles: Default::default(),
+            pending: Default::default(),
+        }
+    }
+
+    pub fn track(&self, handle: AbortHandle) {
+        let mut handles = self.handles.lock().unwrap();
+        handles.retain(|handle| !handle.is_finished());
+        handles.push(handle);
+    }
+
+    /// Marks a node as pending, returns true if it was not pending before.
+    fn begin(&self, node_id: Uuid) -> bool {
+        let mut pending = self.pending.lock().unwrap();
+        let count = pending.entry(node_id).or_default();
+        *count += 1;
+        *count == 1
+    }
+
+    /// Returns true if the node is no longer pending.
+    fn end(&self, node_id: Uuid) -> bool {
+        let mut pending = self.pending.lock().unwrap();
+        let Some(count) = pending.get_mut(&node_id) else {
+            return false;
+        };
+        *count -= 1;
+        if *count == 0 {
+            pending.remove(&node_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn spawn<F>(&self, future: F) -> AbortHandle
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let view = self.view;
+        let boundary = self.boundary.clone();
+        let handle = tokio::spawn(async move {
+            if let Err(payload) = AssertUnwindSafe(future).catch_unwind().await {
+                boundary::report(boundary.as_ref(), ViewError::from_panic(view, payload));
+            }
+        })
+        .abort_handle();
+        self.track(handle.clone());
+        handle
+    }
+
+    pub fn spawn_pending<F>(
+        self: &Arc<Self>,
+        node_id: Uuid,
+        rendering_queue: &Arc<RenderingQueue>,
+        future: F,
+    ) where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        if self.begin(node_id) {
+            rendering_queue.enqueue_patch(NodePatch::SetAttr {
+                node_id,
+                attr: PENDING_ATTR.to_string(),
+                value: String::new(),
+            });
+        }
+
+        let tasks = Arc::clone(self);
+        let rendering_queue = Arc::clone(rendering_queue);
+        self.spawn(async move {
+            // the element is no longer pending even if the handler panicked
+            let result = AssertUnwindSafe(future).catch_unwind().await;
+            if tasks.end(node_id) {
+                rendering_queue.enqueue_patch(NodePatch::RemoveAttr {
+                    node_id,
+                    attr: PENDING_ATTR.to_string(),
+                });
+            }
+            if let Err(payload) = result {
+                std::panic::resume_unwind(payload);
+            }
+        });
+    }
+
+    pub fn abort_all(&self) {
+        for handle in self.handles.lock().unwrap().drain(..) {
+            handle.abort();
+        }
+        self.pending.lock().unwrap().clear();
+    }
+}
@@ -0,0 +1,663 @@
+//! Typed builders for the standard HTML elements.
+//!
+//! Each element only exposes the attributes and events it supports, on top of the global ones
+//! from [`HtmlElement`]:
+//!
+//! ```
+//! use blastview::elements::*;
+//!
+//! let field = input()
+//!     .r#type(InputType::Email)
+//!     .placeholder("you@example.com")
+//!     .required(true)
+//!     .on_input(|| {});
+//! let form = form().method(FormMethod::Post).child(label().r#for("email")).child(field);
+//! ```
+//!
+//! Void elements take no children:
+//!
+//! ```compile_fail
+//! use blastview::elements::*;
+//!
+//! let image = img().src("/logo.png").child("logo");
+//! ```
+//!
+//! Nor can the node of an element be changed other than through its methods:
+//!
+//! ```compile_fail
+//! use blastview::elements::*;
+//!
+//! let link = a().map(|node| node.attr("onclick", "steal()"));
+//! ```
+//!
+//! Bindings and attributes without a typed method remain available through
+//! [`into_inner`](DivElement::into_inner), which returns the underlying [`ElementNode`].
+
+mod values;
+
+use std::{fmt::Display, future::Future};
+
+use crate::{
+    html::tags::{HTML_TAGS, VOID_TAGS},
+    node::{ElementNode, Node},
+};
+use sealed::WrapsNode;
+pub use values::{ButtonType, Direction, FormMethod, InputType, Loading};
+
+macro_rules! attributes {
+    ($vis:vis fn $( $method:ident $(= $name:literal)? : $kind:tt ),* $(,)?) => {
+        $( attributes!(@one $vis fn $method, attributes!(@name $method $(, $name)?), $kind); )*
+    };
+    (@name $method:ident, $name:literal) => { $name };
+    (@name $method:ident) => { stringify!($method) };
+    (@one $vis:vis fn $method:ident, $name:expr, text) => {
+        $vis fn $method(self, value: impl Display) -> Self {
+            self.map(|node| node.attr($name, &value.to_string()))
+        }
+    };
+    (@one $vis:vis fn $method:ident, $name:expr, bool) => {
+        $vis fn $method(self, value: bool) -> Self {
+            self.map(|node| node.flag($name, value))
+        }
+    };
+    (@one $vis:vis fn $method:ident, $name:expr, $ty:ident) => {
+        $vis fn $method(self, value: $ty) -> Self {
+            self.map(|node| node.attr($name, value.as_str()))
+        }
+    };
+}
+
+macro_rules! events {
+    ($vis:vis fn $( $method:ident / $async_method:ident = $name:literal ),* $(,)?) => {$(
+        $vis fn $method<F>(self, handler: F) -> Self
+        where
+            F: Fn() + Send + Sync + 'static,
+        {
+            self.map(|node| node.on($name, handler))
+        }
+
+        $vis fn $async_method<F, Fut>(self, handler: F) -> Self
+        where
+            F: Fn() -> Fut + Send + Sync + 'static,
+            Fut: Future<Output = ()> + Send + 'static,
+        {
+            self.map(|node| node.on_async($name, handler))
+        }
+    )*};
+}
+
+macro_rules! elements {
+    (@children $ty:ident void) => {};
+    (@children $ty:ident) => {
+        impl $ty {
+            pub fn child(self, node: impl Into<Node>) -> Self {
+                self.map(|element| element.child(node))
+            }
+
+            pub fn children<N: Into<Node>>(self, nodes: impl IntoIterator<Item = N>) -> Self {
+                self.map(|element| element.children(nodes))
+            }
+        }
+    };
+    (@is_void void) => { true };
+    (@is_void) => { false };
+    ($(
+        $tag:ident: $ty:ident $([$void:ident])? {
+            $( $attr:ident $(= $attr_name:literal)? : $kind:tt ),* $(,)?
+            $( ; $( $event:ident / $async_event:ident = $event_name:literal ),* $(,)? )?
+        }
+    )*) => {
+        // the builders follow the tag data shared with the macros
+        const _: () = assert!(
+            matches_tags(&[$( (stringify!($tag), elements!(@is_void $($void)?)) ),*]),
+            "element builders must match `html::tags`",
+        );
+
+        $(
+        #[doc = concat!("A `<", stringify!($tag), ">` element.")]
+        #[derive(Debug, Clone)]
+        pub struct $ty(ElementNode);
+
+        #[doc = concat!("Creates a `<", stringify!($tag), ">` element.")]
+        pub fn $tag() -> $ty {
+            $ty(ElementNode::new(stringify!($tag)))
+        }
+
+        impl WrapsNode for $ty {
+            fn map(self, f: impl FnOnce(ElementNode) -> ElementNode) -> Self {
+                Self(f(self.0))
+            }
+        }
+
+        impl HtmlElement for $ty {}
+
+        impl From<$ty> for Node {
+            fn from(element: $ty) -> Self {
+                element.0.into()
+            }
+        }
+
+        impl $ty {
+            attributes!(pub fn $( $attr $(= $attr_name)? : $kind ),*);
+            $( events!(pub fn $( $event / $async_event = $event_name ),*); )?
+
+            pub fn into_inner(self) -> ElementNode {
+                self.0
+            }
+        }
+
+        elements!(@children $ty $($void)?);
+    )*};
+}
+
+/// Whether there is a builder for every HTML element, each given by its tag and whether it is
+/// void, as listed in the tag data.
+const fn matches_tags(builders: &[(&str, bool)]) -> bool {
+    if builders.len() != HTML_TAGS.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < builders.len() {
+        let (tag, is_void) = builders[i];
+        if !contains(HTML_TAGS, tag) || contains(VOID_TAGS, tag) != is_void {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn contains(tags: &[&str], tag: &str) -> bool {
+    let mut i = 0;
+    while i < tags.len() {
+        if same_bytes(tags[i].as_bytes(), tag.as_bytes()) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+// `str` comparisons are not yet available in constants
+const fn same_bytes(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+mod sealed {
+    use crate::node::ElementNode;
+
+    /// Gives the element methods access to the node they build, without exposing it to users.
+    pub trait WrapsNode {
+        fn map(self, f: impl FnOnce(ElementNode) -> ElementNode) -> Self;
+    }
+}
+
+/// The attributes and events shared by every element.
+///
+/// It is implemented for the builders of this module only.
+pub trait HtmlElement: WrapsNode + Into<Node> + Sized {
+    attributes! {fn
+        accesskey: text,
+        autofocus: bool,
+        class: text,
+        contenteditable: text,
+        dir: Direction,
+        draggable: text,
+        hidden: bool,
+        id: text,
+        inert: bool,
+        lang: text,
+        role: text,
+        spellcheck: text,
+        style: text,
+        tabindex: text,
+        title: text,
+        translate: text,
+    }
+
+    /// Sets a `data-*` attribute.
+    fn data(self, key: &str, value: impl Display) -> Self {
+        self.map(|node| node.attr(&format!("data-{key}"), &value.to_string()))
+    }
+
+    /// Sets an `aria-*` attribute.
+    fn aria(self, key: &str, value: impl Display) -> Self {
+        self.map(|node| node.attr(&format!("aria-{key}"), &value.to_string()))
+    }
+
+    events! {fn
+        on_animationend / on_animationend_async = "animationend",
+        on_blur / on_blur_async = "blur",
+        on_change / on_change_async = "change",
+        on_click / on_click_async = "click",
+        on_contextmenu / on_contextmenu_async = "contextmenu",
+        on_dblclick / on_dblclick_async = "dblclick",
+        on_dragend / on_dragend_async = "dragend",
+        on_dragstart / on_dragstart_async = "dragstart",
+        on_drop / on_drop_async = "drop",
+        on_focus / on_focus_async = "focus",
+        on_focusin / on_focusin_async = "focusin",
+        on_focusout / on_focusout_async = "focusout",
+        on_input / on_input_async = "input",
+        on_invalid / on_invalid_async = "invalid",
+        on_keydown / on_keydown_async = "keydown",
+        on_keyup / on_keyup_async = "keyup",
+        on_mousedown / on_mousedown_async = "mousedown",
+        on_mouseenter / on_mouseenter_async = "mouseenter",
+        on_mouseleave / on_mouseleave_async = "mouseleave",
+        on_mousemove / on_mousemove_async = "mousemove",
+        on_mouseout / on_mouseout_async = "mouseout",
+        on_mouseover / on_mouseover_async = "mouseover",
+        on_mouseup / on_mouseup_async = "mouseup",
+        on_pointerdown / on_pointerdown_async = "pointerdown",
+        on_pointerup / on_pointerup_async = "pointerup",
+        on_scroll / on_scroll_async = "scroll",
+        on_touchend / on_touchend_async = "touchend",
+        on_touchstart / on_touchstart_async = "touchstart",
+        on_transitionend / on_transitionend_async = "transitionend",
+        on_wheel / on_wheel_async = "wheel",
+    }
+}
+
+elements! {
+    a: AElement {
+        download: text,
+        href: text,
+        hreflang: text,
+        referrerpolicy: text,
+        rel: text,
+        target: text,
+        r#type = "type": text,
+    }
+    abbr: AbbrElement {}
+    address: AddressElement {}
+    area: AreaElement [void] {
+        alt: text,
+        coords: text,
+        download: text,
+        href: text,
+        referrerpolicy: text,
+        rel: text,
+        shape: text,
+        target: text,
+    }
+    article: ArticleElement {}
+    aside: AsideElement {}
+    audio: AudioElement {
+        autoplay: bool,
+        controls: bool,
+        crossorigin: text,
+        r#loop = "loop": bool,
+        muted: bool,
+        preload: text,
+        src: text;
+        on_ended / on_ended_async = "ended",
+        on_pause / on_pause_async = "pause",
+        on_play / on_play_async = "play",
+        on_timeupdate / on_timeupdate_async = "timeupdate",
+        on_volumechange / on_volumechange_async = "volumechange",
+    }
+    b: BElement {}
+    base: BaseElement [void] {
+        href: text,
+        target: text,
+    }
+    bdi: BdiElement {}
+    bdo: BdoElement {}
+    blockquote: BlockquoteElement {
+        cite: text,
+    }
+    body: BodyElement {}
+    br: BrElement [void] {}
+    button: ButtonElement {
+        disabled: bool,
+        form: text,
+        formaction: text,
+        formmethod: FormMethod,
+        formnovalidate: bool,
+        name: text,
+        popovertarget: text,
+        r#type = "type": ButtonType,
+        value: text,
+    }
+    canvas: CanvasElement {
+        height: text,
+        width: text,
+    }
+    caption: CaptionElement {}
+    cite: CiteElement {}
+    code: CodeElement {}
+    col: ColElement [void] {
+        span: text,
+    }
+    colgroup: ColgroupElement {
+        span: text,
+    }
+    data: DataElement {
+        value: text,
+    }
+    datalist: DatalistElement {}
+    dd: DdElement {}
+    del: DelElement {
+        cite: text,
+        datetime: text,
+    }
+    details: DetailsElement {
+        name: text,
+        open: bool;
+        on_toggle / on_toggle_async = "toggle",
+    }
+    dfn: DfnElement {}
+    dialog: DialogElement {
+        open: bool;
+        on_cancel / on_cancel_async = "cancel",
+        on_close / on_close_async = "close",
+    }
+    div: DivElement {}
+    dl: DlElement {}
+    dt: DtElement {}
+    em: EmElement {}
+    embed: EmbedElement [void] {
+        height: text,
+        src: text,
+        r#type = "type": text,
+        width: text,
+    }
+    fieldset: FieldsetElement {
+        disabled: bool,
+        form: text,
+        name: text,
+    }
+    figcaption: FigcaptionElement {}
+    figure: FigureElement {}
+    footer: FooterElement {}
+    form: FormElement {
+        action: text,
+        autocomplete: text,
+        enctype: text,
+        method: FormMethod,
+        name: text,
+        novalidate: bool,
+        target: text;
+        on_reset / on_reset_async = "reset",
+        on_submit / on_submit_async = "submit",
+    }
+    h1: H1Element {}
+    h2: H2Element {}
+    h3: H3Element {}
+    h4: H4Element {}
+    h5: H5Element {}
+    h6: H6Element {}
+    head: HeadElement {}
+    header: HeaderElement {}
+    hgroup: HgroupElement {}
+    hr: HrElement [void] {}
+    html: HtmlRootElement {
+        xmlns: text,
+    }
+    i: IElement {}
+    iframe: IframeElement {
+        allow: text,
+        allowfullscreen: bool,
+        height: text,
+        loading: Loading,
+        name: text,
+        referrerpolicy: text,
+        sandbox: text,
+        src: text,
+        srcdoc: text,
+        width: text;
+        on_load / on_load_async = "load",
+    }
+    img: ImgElement [void] {
+        alt: text,
+        crossorigin: text,
+        decoding: text,
+        height: text,
+        ismap: bool,
+        loading: Loading,
+        referrerpolicy: text,
+        sizes: text,
+        src: text,
+        srcset: text,
+        usemap: text,
+        width: text;
+        on_error / on_error_async = "error",
+        on_load / on_load_async = "load",
+    }
+    input: InputElement [void] {
+        accept: text,
+        alt: text,
+        autocomplete: text,
+        checked: bool,
+        disabled: bool,
+        form: text,
+        formaction: text,
+        formmethod: FormMethod,
+        formnovalidate: bool,
+        list: text,
+        max: text,
+        maxlength: text,
+        min: text,
+        minlength: text,
+        multiple: bool,
+        name: text,
+        pattern: text,
+        placeholder: text,
+        readonly: bool,
+        required: bool,
+        size: text,
+        src: text,
+        step: text,
+        r#type = "type": InputType,
+        value: text;
+        on_select / on_select_async = "select",
+    }
+    ins: InsElement {
+        cite: text,
+        datetime: text,
+    }
+    kbd: KbdElement {}
+    label: LabelElement {
+        r#for = "for": text,
+    }
+    legend: LegendElement {}
+    li: LiElement {
+        value: text,
+    }
+    link: LinkElement [void] {
+        r#as = "as": text,
+        crossorigin: text,
+        href: text,
+        hreflang: text,
+        integrity: text,
+        media: text,
+        referrerpolicy: text,
+        rel: text,
+        sizes: text,
+        r#type = "type": text,
+    }
+    main: MainElement {}
+    map: MapElement {
+        name: text,
+    }
+    mark: MarkElement {}
+    menu: MenuElement {}
+    meta: MetaElement [void] {
+        charset: text,
+        content: text,
+        http_equiv = "http-equiv": text,
+        media: text,
+        name: text,
+    }
+    meter: MeterElement {
+        form: text,
+        high: text,
+        low: text,
+        max: text,
+        min: text,
+        optimum: text,
+        value: text,
+    }
+    nav: NavElement {}
+    noscript: NoscriptElement {}
+    object: ObjectElement {
+        data_url = "data": text,
+        form: text,
+        height: text,
+        name: text,
+        r#type = "type": text,
+        width: text,
+    }
+    ol: OlElement {
+        reversed: bool,
+        start: text,
+        r#type = "type": text,
+    }
+    optgroup: OptgroupElement {
+        disabled: bool,
+        label: text,
+    }
+    option: OptionElement {
+        disabled: bool,
+        label: text,
+        selected: bool,
+        value: text,
+    }
+    output: OutputElement {
+        r#for = "for": text,
+        form: text,
+        name: text,
+    }
+    p: PElement {}
+    picture: PictureElement {}
+    pre: PreElement {}
+    progress: ProgressElement {
+        max: text,
+        value: text,
+    }
+    q: QElement {
+        cite: text,
+    }
+    rp: RpElement {}
+    rt: RtElement {}
+    ruby: RubyElement {}
+    s: SElement {}
+    samp: SampElement {}
+    script: ScriptElement {
+        r#async = "async": bool,
+        crossorigin: text,
+        defer: bool,
+        integrity: text,
+        nomodule: bool,
+        referrerpolicy: text,
+        src: text,
+        r#type = "type": text,
+    }
+    search: SearchElement {}
+    section: SectionElement {}
+    select: SelectElement {
+        autocomplete: text,
+        disabled: bool,
+        form: text,
+        multiple: bool,
+        name: text,
+        required: bool,
+        size: text,
+    }
+    slot: SlotElement {
+        name: text,
+    }
+    small: SmallElement {}
+    source: SourceElement [void] {
+        media: text,
+        sizes: text,
+        src: text,
+        srcset: text,
+        r#type = "type": text,
+    }
+    span: SpanElement {}
+    strong: StrongElement {}
+    style: StyleElement {
+        media: text,
+    }
+    sub: SubElement {}
+    summary: SummaryElement {}
+    sup: SupElement {}
+    table: TableElement {}
+    tbody: TbodyElement {}
+    td: TdElement {
+        colspan: text,
+        headers: text,
+        rowspan: text,
+    }
+    template: TemplateElement {}
+    textarea: TextareaElement {
+        autocomplete: text,
+        cols: text,
+        disabled: bool,
+        form: text,
+        maxlength: text,
+        minlength: text,
+        name: text,
+        placeholder: text,
+        readonly: bool,
+        required: bool,
+        rows: text,
+        wrap: text;
+        on_select / on_select_async = "select",
+    }
+    tfoot: TfootElement {}
+    th: ThElement {
+        abbr: text,
+        colspan: text,
+        headers: text,
+        rowspan: text,
+        scope: text,
+    }
+    thead: TheadElement {}
+    time: TimeElement {
+        datetime: text,
+    }
+    title: TitleElement {}
+    tr: TrElement {}
+    track: TrackElement [void] {
+        default: bool,
+        kind: text,
+        label: text,
+        src: text,
+        srclang: text,
+    }
+    u: UElement {}
+    ul: UlElement {}
+    var: VarElement {}
+    video: VideoElement {
+        autoplay: bool,
+        controls: bool,
+        crossorigin: text,
+        height: text,
+        r#loop = "loop": bool,
+        muted: bool,
+        playsinline: bool,
+        poster: text,
+        preload: text,
+        src: text,
+        width: text;
+        on_ended / on_ended_async = "ended",
+        on_pause / on_pause_async = "pause",
+        on_play / on_play_async = "play",
+        on_timeupdate / on_timeupdate_async = "timeupdate",
+        on_volumechange / on_volumechange_async = "volumechange",
+    }
+    wbr: WbrElement [void] {}
+}
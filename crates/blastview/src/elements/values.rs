@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+macro_rules! values {
+    ($(
+        $(#[$doc:meta])*
+        $ty:ident { $( $variant:ident = $value:literal ),* $(,)? }
+    )*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum $ty {
+                $( $variant, )*
+            }
+
+            impl $ty {
+                pub fn as_str(&self) -> &'static str {
+                    match self {
+                        $( Self::$variant => $value, )*
+                    }
+                }
+            }
+
+            impl Display for $ty {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+        )*
+    };
+}
+
+values! {
+    /// The `type` of an `<input>`.
+    InputType {
+        Button = "button",
+        Checkbox = "checkbox",
+        Color = "color",
+        Date = "date",
+        DatetimeLocal = "datetime-local",
+        Email = "email",
+        File = "file",
+        Hidden = "hidden",
+        Image = "image",
+        Month = "month",
+        Number = "number",
+        Password = "password",
+        Radio = "radio",
+        Range = "range",
+        Reset = "reset",
+        Search = "search",
+        Submit = "submit",
+        Tel = "tel",
+        Text = "text",
+        Time = "time",
+        Url = "url",
+        Week = "week",
+    }
+
+    /// The `type` of a `<button>`.
+    ButtonType {
+        Button = "button",
+        Reset = "reset",
+        Submit = "submit",
+    }
+
+    /// The `method` of a `<form>`.
+    FormMethod {
+        Dialog = "dialog",
+        Get = "get",
+        Post = "post",
+    }
+
+    /// The `loading` of an `<img>` or an `<iframe>`.
+    Loading {
+        Eager = "eager",
+        Lazy = "lazy",
+    }
+
+    /// The `dir` of any element.
+    Direction {
+        Auto = "auto",
+        Ltr = "ltr",
+        Rtl = "rtl",
+    }
+}
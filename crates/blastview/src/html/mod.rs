@@ -0,0 +1,65 @@
+mod sanitizer;
+pub(crate) mod tokenizer;
+pub(crate) mod tree;
+
+pub use blastview_tags as tags;
+pub use sanitizer::Sanitizer;
+pub use tree::ParseError;
+
+/// HTML that is rendered as is, without escaping.
+///
+/// It can only be made from HTML the application vouches for, or by sanitizing untrusted input.
+/// See [`crate::node::Node::RawHtml`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trusted(String);
+
+impl Trusted {
+    /// Trusts HTML as is, such as the output of a Markdown renderer or content from a CMS.
+    ///
+    /// Never pass HTML that may hold user input, which would let it run scripts on the page.
+    pub fn assume_safe(html: impl Into<String>) -> Self {
+        Self(html.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Attributes holding a URL, whose scheme must be checked before rendering untrusted values.
+pub const URL_ATTRIBUTES: &[&str] = &[
+    "action",
+    "background",
+    "cite",
+    "data",
+    "formaction",
+    "href",
+    "poster",
+    "src",
+    "xlink:href",
+];
+
+/// Whether an attribute, named in any case, holds a URL.
+pub fn is_url_attribute(name: &str) -> bool {
+    URL_ATTRIBUTES
+        .iter()
+        .any(|attr| attr.eq_ignore_ascii_case(name))
+}
+
+/// The scheme of a URL, lowercased, or `None` for relative URLs, parsed the way browsers do.
+pub fn url_scheme(url: &str) -> Option<String> {
+    // browsers ignore surrounding spaces and control characters, and tabs or newlines anywhere
+    let url: String = url
+        .trim_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+
+    let end = url.find(':')?;
+    let scheme = &url[..end];
+    let is_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    is_scheme.then(|| scheme.to_ascii_lowercase())
+}
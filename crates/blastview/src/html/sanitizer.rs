@@ -0,0 +1,197 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    html::{
+        Trusted, is_url_attribute,
+        tokenizer::{Token, Tokenizer},
+        url_scheme,
+    },
+    node::ElementKind,
+};
+
+/// Turns untrusted HTML into [`Trusted`] HTML by keeping allowed tags and attributes only.
+///
+/// Disallowed tags are removed but their text is kept, except for `<script>` and `<style>`
+/// whose contents are removed with them. Comments are removed, and unclosed tags are closed,
+/// so that the output cannot leak into the surrounding page.
+#[derive(Debug, Clone)]
+pub struct Sanitizer {
+    tags: BTreeSet<String>,
+    attrs: BTreeSet<String>,
+    url_schemes: BTreeSet<String>,
+}
+
+impl Default for Sanitizer {
+    /// Allows common formatting, such as the output of a Markdown renderer.
+    fn default() -> Self {
+        Self::empty()
+            .allow_tags([
+                "a",
+                "abbr",
+                "b",
+                "blockquote",
+                "br",
+                "code",
+                "del",
+                "em",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "hr",
+                "i",
+                "img",
+                "li",
+                "ol",
+                "p",
+                "pre",
+                "s",
+                "strong",
+                "sub",
+                "sup",
+                "table",
+                "tbody",
+                "td",
+                "th",
+                "thead",
+                "tr",
+                "ul",
+            ])
+            .allow_attrs(["alt", "cite", "href", "src", "title"])
+            .allow_url_schemes(["http", "https", "mailto"])
+    }
+}
+
+impl Sanitizer {
+    /// A sanitizer allowing nothing but text.
+    pub fn empty() -> Self {
+        Self {
+            tags: Default::default(),
+            attrs: Default::default(),
+            url_schemes: Default::default(),
+        }
+    }
+
+    pub fn allow_tags<S: AsRef<str>>(mut self, tags: impl IntoIterator<Item = S>) -> Self {
+        self.tags.extend(
+            tags.into_iter()
+                .map(|tag| tag.as_ref().to_ascii_lowercase()),
+        );
+        self
+    }
+
+    /// Allows attributes on every allowed tag. Event handlers such as `onclick` are never allowed.
+    pub fn allow_attrs<S: AsRef<str>>(mut self, attrs: impl IntoIterator<Item = S>) -> Self {
+        self.attrs.extend(
+            attrs
+                .into_iter()
+                .map(|attr| attr.as_ref().to_ascii_lowercase())
+                .filter(|attr| !attr.starts_with("on")),
+        );
+        self
+    }
+
+    /// Allows URL schemes in attributes holding a URL, such as `href` or `src`. Relative URLs
+    /// are always allowed.
+    pub fn allow_url_schemes<S: AsRef<str>>(
+        mut self,
+        schemes: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.url_schemes.extend(
+            schemes
+                .into_iter()
+                .map(|scheme| scheme.as_ref().to_ascii_lowercase()),
+        );
+        self
+    }
+
+    pub fn sanitize(&self, html: &str) -> Trusted {
+        let mut output = String::with_capacity(html.len());
+        let mut open: Vec<String> = vec![];
+        // set while inside a removed `<script>` or `<style>`
+        let mut skipping = None;
+
+        for token in Tokenizer::new(html) {
+            let Ok((_, token)) = token else {
+                // only a comment, tag or attribute value left unterminated by the end of the
+                // input is an error, and browsers drop it as well
+                break;
+            };
+
+            match token {
+                Token::StartTag { name, attrs, .. } => {
+                    if !self.tags.contains(&name) {
+                        if ElementKind::of(&name) == ElementKind::RawText {
+                            skipping = Some(name);
+                        }
+                        continue;
+                    }
+
+                    output.push('<');
+                    output.push_str(&name);
+                    let mut seen: Vec<String> = vec![];
+                    for attr in attrs {
+                        // browsers keep the first of duplicate attributes, even a disallowed one
+                        if seen.contains(&attr.name) {
+                            continue;
+                        }
+                        seen.push(attr.name.clone());
+                        let value = attr.value.unwrap_or_default();
+                        if !self.allows_attr(&attr.name, &value) {
+                            continue;
+                        }
+                        output.push(' ');
+                        output.push_str(&attr.name);
+                        output.push_str("=\"");
+                        output.push_str(&html_escape::encode_double_quoted_attribute(&value));
+                        output.push('"');
+                    }
+                    output.push('>');
+
+                    if ElementKind::of(&name) != ElementKind::Void {
+                        open.push(name);
+                    }
+                }
+                Token::EndTag { name } => {
+                    if skipping.as_ref() == Some(&name) {
+                        skipping = None;
+                    }
+                    // end tags without a matching open tag are dropped,
+                    // and close the tags left open inside them otherwise
+                    if let Some(at) = open.iter().rposition(|tag| *tag == name) {
+                        for tag in open.drain(at..).rev() {
+                            close(&mut output, &tag);
+                        }
+                    }
+                }
+                Token::Text(text) if skipping.is_none() => {
+                    output.push_str(&html_escape::encode_text(&text));
+                }
+                Token::Text(_) | Token::Comment(_) | Token::Doctype(_) => {}
+            }
+        }
+
+        for tag in open.iter().rev() {
+            close(&mut output, tag);
+        }
+        Trusted(output)
+    }
+
+    fn allows_attr(&self, name: &str, value: &str) -> bool {
+        if !self.attrs.contains(name) {
+            return false;
+        }
+        if !is_url_attribute(name) {
+            return true;
+        }
+        url_scheme(value).is_none_or(|scheme| self.url_schemes.contains(&scheme))
+    }
+}
+
+fn close(output: &mut String, tag: &str) {
+    output.push_str("</");
+    output.push_str(tag);
+    output.push('>');
+}
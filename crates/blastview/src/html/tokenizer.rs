@@ -0,0 +1,282 @@
+use std::borrow::Cow;
+
+use crate::node::ElementKind;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token<'a> {
+    StartTag {
+        name: String,
+        attrs: Vec<Attribute>,
+        self_closing: bool,
+    },
+    EndTag {
+        name: String,
+    },
+    /// Text with its character references decoded.
+    Text(Cow<'a, str>),
+    Comment(&'a str),
+    Doctype(&'a str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Attribute {
+    /// The byte offset at which the attribute starts.
+    pub offset: usize,
+    pub name: String,
+    /// The decoded value, or `None` for attributes written without one.
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SyntaxError {
+    pub offset: usize,
+    pub message: &'static str,
+}
+
+/// Splits HTML into tokens, each paired with the byte offset at which it starts.
+///
+/// Tag and attribute names are lowercased. Input that would start a tag without forming one,
+/// such as `a < b`, is read as text, like browsers do.
+pub(crate) struct Tokenizer<'a> {
+    input: &'a str,
+    pos: usize,
+    // set after the start tag of a `<script>`, `<textarea>` or similar element
+    raw_text: Option<(String, ElementKind)>,
+    failed: bool,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            pos: 0,
+            raw_text: None,
+            failed: false,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn error(&mut self, offset: usize, message: &'static str) -> SyntaxError {
+        self.failed = true;
+        SyntaxError { offset, message }
+    }
+
+    fn raw_text(&mut self, tag: &str, kind: ElementKind) -> Token<'a> {
+        let rest = self.rest();
+        let end = find_end_tag(rest, tag).unwrap_or(rest.len());
+        self.pos += end;
+
+        let text = &rest[..end];
+        if kind == ElementKind::EscapableRawText {
+            Token::Text(html_escape::decode_html_entities(text))
+        } else {
+            Token::Text(Cow::Borrowed(text))
+        }
+    }
+
+    fn text(&mut self) -> Token<'a> {
+        let rest = self.rest();
+        // the first character is text, even if it is a `<`
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|&(at, _)| starts_tag(&rest[at..]))
+            .map_or(rest.len(), |(at, _)| at);
+        self.pos += end;
+        Token::Text(html_escape::decode_html_entities(&rest[..end]))
+    }
+
+    fn markup(&mut self, start: usize) -> Result<Token<'a>, SyntaxError> {
+        let rest = self.rest();
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let Some(end) = comment.find("-->") else {
+                return Err(self.error(start, "unterminated comment"));
+            };
+            self.pos += 4 + end + 3;
+            return Ok(Token::Comment(&comment[..end]));
+        }
+
+        let declaration = &rest[2..];
+        let Some(end) = declaration.find('>') else {
+            return Err(self.error(start, "unterminated declaration"));
+        };
+        self.pos += 2 + end + 1;
+        Ok(Token::Doctype(declaration[..end].trim()))
+    }
+
+    fn end_tag(&mut self, start: usize) -> Result<Token<'a>, SyntaxError> {
+        self.pos += 2;
+        let name = self.name();
+        // anything up to the `>`, such as attributes, is ignored
+        let Some(end) = self.rest().find('>') else {
+            return Err(self.error(start, "unterminated end tag"));
+        };
+        self.pos += end + 1;
+        Ok(Token::EndTag { name })
+    }
+
+    fn start_tag(&mut self, start: usize) -> Result<Token<'a>, SyntaxError> {
+        self.pos += 1;
+        let name = self.name();
+        let mut attrs: Vec<Attribute> = vec![];
+
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Err(self.error(start, "unterminated start tag"));
+            } else if rest.starts_with('>') {
+                self.pos += 1;
+                return Ok(self.open(name, attrs, false));
+            } else if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok(self.open(name, attrs, true));
+            } else if rest.starts_with('/') {
+                self.pos += 1;
+                continue;
+            }
+
+            // duplicates are kept, for consumers to either reject them or keep the first one
+            // as browsers do
+            attrs.push(self.attribute()?);
+        }
+    }
+
+    fn open(&mut self, name: String, attrs: Vec<Attribute>, self_closing: bool) -> Token<'a> {
+        let kind = ElementKind::of(&name);
+        if matches!(kind, ElementKind::RawText | ElementKind::EscapableRawText) {
+            self.raw_text = Some((name.clone(), kind));
+        }
+        Token::StartTag {
+            name,
+            attrs,
+            self_closing,
+        }
+    }
+
+    fn attribute(&mut self) -> Result<Attribute, SyntaxError> {
+        let offset = self.pos;
+        let rest = self.rest();
+        // a leading `=` is part of the name
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| c.is_ascii_whitespace() || matches!(c, '/' | '>' | '='))
+            .map_or(rest.len(), |(at, _)| at);
+        let name = rest[..end].to_ascii_lowercase();
+        self.pos += end;
+
+        self.skip_whitespace();
+        if !self.rest().starts_with('=') {
+            return Ok(Attribute {
+                offset,
+                name,
+                value: None,
+            });
+        }
+        self.pos += 1;
+        self.skip_whitespace();
+
+        let rest = self.rest();
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let Some(end) = rest[1..].find(quote) else {
+                    return Err(self.error(self.pos, "unterminated attribute value"));
+                };
+                self.pos += 1 + end + 1;
+                &rest[1..1 + end]
+            }
+            _ => {
+                let end = rest
+                    .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                    .unwrap_or(rest.len());
+                self.pos += end;
+                &rest[..end]
+            }
+        };
+
+        Ok(Attribute {
+            offset,
+            name,
+            value: Some(html_escape::decode_html_entities(value).into_owned()),
+        })
+    }
+
+    fn name(&mut self) -> String {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_ascii_whitespace() || matches!(c, '/' | '>'))
+            .unwrap_or(rest.len());
+        self.pos += end;
+        rest[..end].to_ascii_lowercase()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len()
+            - rest
+                .trim_start_matches(|c: char| c.is_ascii_whitespace())
+                .len();
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Result<(usize, Token<'a>), SyntaxError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.pos;
+        if self.failed || self.rest().is_empty() {
+            return None;
+        }
+
+        if let Some((tag, kind)) = self.raw_text.take() {
+            let token = self.raw_text(&tag, kind);
+            if self.pos > start {
+                return Some(Ok((start, token)));
+            }
+        }
+
+        let rest = self.rest();
+        let token = if !starts_tag(rest) {
+            Ok(self.text())
+        } else if rest.starts_with("<!") {
+            self.markup(start)
+        } else if rest.starts_with("</") {
+            self.end_tag(start)
+        } else {
+            self.start_tag(start)
+        };
+        Some(token.map(|token| (start, token)))
+    }
+}
+
+/// Whether the input starts with a tag, an end tag, a comment or a declaration.
+fn starts_tag(input: &str) -> bool {
+    let mut chars = input.chars();
+    if chars.next() != Some('<') {
+        return false;
+    }
+    match chars.next() {
+        Some('/') => chars.next().is_some_and(|c| c.is_ascii_alphabetic()),
+        Some('!') => true,
+        Some(c) => c.is_ascii_alphabetic(),
+        None => false,
+    }
+}
+
+/// The offset of the end tag closing a raw text element, such as `</script>`.
+fn find_end_tag(input: &str, tag: &str) -> Option<usize> {
+    input.match_indices("</").map(|(at, _)| at).find(|&at| {
+        let after = &input[at + 2..];
+        after
+            .get(..tag.len())
+            .is_some_and(|name| name.eq_ignore_ascii_case(tag))
+            && after[tag.len()..]
+                .chars()
+                .next()
+                .is_none_or(|c| c.is_ascii_whitespace() || matches!(c, '/' | '>'))
+    })
+}
@@ -0,0 +1,174 @@
+use std::fmt::Display;
+
+use crate::{
+    html::tokenizer::{Attribute, Token, Tokenizer},
+    node::ElementKind,
+};
+
+/// A node of parsed HTML, with the byte offset at which it starts.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TreeNode {
+    Text {
+        offset: usize,
+        text: String,
+    },
+    Element {
+        offset: usize,
+        tag: String,
+        attrs: Vec<Attribute>,
+        children: Vec<TreeNode>,
+    },
+}
+
+/// An error in parsed HTML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let (line, column) = position(input, offset);
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// The line of the error, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column of the error, in characters, starting at 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The line and column, both starting at 1, of a byte offset in `input`.
+fn position(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |at| at + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+struct Open {
+    offset: usize,
+    tag: String,
+    attrs: Vec<Attribute>,
+    children: Vec<TreeNode>,
+}
+
+impl Open {
+    fn close(self) -> TreeNode {
+        TreeNode::Element {
+            offset: self.offset,
+            tag: self.tag,
+            attrs: self.attrs,
+            children: self.children,
+        }
+    }
+}
+
+/// Parses an HTML fragment into its root nodes, dropping comments and doctypes.
+///
+/// Unlike browsers, which repair any markup, every element must be closed by a matching end
+/// tag, unless it is void or self-closing. Text made only of whitespace across lines, such as
+/// indentation between tags, is dropped.
+pub(crate) fn parse(input: &str) -> Result<Vec<TreeNode>, ParseError> {
+    parse_checked(input, |_, _| Ok(()))
+}
+
+/// Parses like [`parse`], passing each token with its offset to `check` as soon as it is read,
+/// so that its errors are reported in document order along with syntax errors.
+pub(crate) fn parse_checked<F>(input: &str, mut check: F) -> Result<Vec<TreeNode>, ParseError>
+where
+    F: FnMut(usize, &Token) -> Result<(), ParseError>,
+{
+    let mut roots = vec![];
+    let mut open: Vec<Open> = vec![];
+
+    for token in Tokenizer::new(input) {
+        let (offset, token) =
+            token.map_err(|error| ParseError::new(input, error.offset, error.message))?;
+        check(offset, &token)?;
+        let siblings = match open.last_mut() {
+            Some(parent) => &mut parent.children,
+            None => &mut roots,
+        };
+
+        match token {
+            Token::StartTag {
+                name,
+                attrs,
+                self_closing,
+            } => {
+                let duplicate = attrs.iter().enumerate().find(|(at, attr)| {
+                    attrs[..*at].iter().any(|earlier| earlier.name == attr.name)
+                });
+                if let Some((_, attr)) = duplicate {
+                    return Err(ParseError::new(input, attr.offset, "duplicate attribute"));
+                }
+                let element = Open {
+                    offset,
+                    tag: name,
+                    attrs,
+                    children: vec![],
+                };
+                if self_closing || ElementKind::of(&element.tag) == ElementKind::Void {
+                    siblings.push(element.close());
+                } else {
+                    open.push(element);
+                }
+            }
+            Token::EndTag { name } => {
+                let Some(element) = open.pop_if(|element| element.tag == name) else {
+                    let message = match open.last() {
+                        Some(element) => {
+                            format!("expected `</{}>`, found `</{name}>`", element.tag)
+                        }
+                        None => format!("unexpected `</{name}>`"),
+                    };
+                    return Err(ParseError::new(input, offset, message));
+                };
+                let siblings = match open.last_mut() {
+                    Some(parent) => &mut parent.children,
+                    None => &mut roots,
+                };
+                siblings.push(element.close());
+            }
+            Token::Text(text) if text.trim().is_empty() && text.contains('\n') => {}
+            Token::Text(text) => siblings.push(TreeNode::Text {
+                offset,
+                text: text.into_owned(),
+            }),
+            Token::Comment(_) | Token::Doctype(_) => {}
+        }
+    }
+
+    match open.pop() {
+        Some(element) => Err(ParseError::new(
+            input,
+            element.offset,
+            format!("unclosed `<{}>`", element.tag),
+        )),
+        None => Ok(roots),
+    }
+}
@@ -1,4 +1,11 @@
 pub mod context;
+pub mod elements;
+pub mod html;
 pub mod node;
 pub mod rendering;
+pub mod signal;
+pub mod template;
 pub mod view;
+
+pub use blastview_macros::{component, html, view};
+pub use rendering::batch;
@@ -0,0 +1,29 @@
+use crate::html::tags::{ESCAPABLE_RAW_TEXT_TAGS, RAW_TEXT_TAGS, VOID_TAGS};
+
+/// How an element is serialized, following the HTML spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementKind {
+    /// Has children and an end tag.
+    Normal,
+    /// Has no children nor end tag, such as `<br>` or `<input>`.
+    Void,
+    /// Contains unescaped text, such as `<script>` or `<style>`.
+    RawText,
+    /// Contains escaped text only, such as `<textarea>` or `<title>`.
+    EscapableRawText,
+}
+
+impl ElementKind {
+    pub fn of(tag: &str) -> Self {
+        let is = |tags: &[&str]| tags.iter().any(|t| t.eq_ignore_ascii_case(tag));
+        if is(VOID_TAGS) {
+            Self::Void
+        } else if is(RAW_TEXT_TAGS) {
+            Self::RawText
+        } else if is(ESCAPABLE_RAW_TEXT_TAGS) {
+            Self::EscapableRawText
+        } else {
+            Self::Normal
+        }
+    }
+}
@@ -1,9 +1,21 @@
+mod kind;
+
 use uuid::Uuid;
 
-use crate::view::ViewRef;
+use crate::{
+    context::events::EventHandler,
+    html::{
+        ParseError, Trusted,
+        tree::{self, TreeNode},
+    },
+    signal::{Bindable, Signal},
+    view::ViewRef,
+};
+pub use kind::ElementKind;
 use std::{
-    collections::HashMap,
+    collections::BTreeMap,
     fmt::{Debug, Display},
+    future::Future,
     sync::Arc,
 };
 
@@ -12,6 +24,11 @@ pub enum Node {
     Text(Box<TextNode>),
     Element(Box<ElementNode>),
     ViewRef(Box<ViewRef>),
+    Signal(Box<SignalNode>),
+    /// HTML rendered without escaping, which the diff replaces as a whole.
+    RawHtml(Box<RawHtmlNode>),
+    /// Sibling nodes without a wrapper element, flattened into the element or view holding them.
+    Fragment(Box<FragmentNode>),
 }
 
 impl Node {
@@ -22,8 +39,89 @@ impl Node {
     pub fn text(text: &str) -> TextNode {
         text.into()
     }
+
+    /// Groups nodes without a wrapper element, such as the items of a list or several view roots.
+    pub fn fragment<N: Into<Node>>(nodes: impl IntoIterator<Item = N>) -> Node {
+        let mut children = vec![];
+        for node in nodes {
+            push_flattened(&mut children, node.into());
+        }
+        Self::Fragment(Box::new(FragmentNode(children)))
+    }
+
+    /// Parses an HTML fragment into elements and text, or a fragment of them for several roots.
+    ///
+    /// Every element must be closed, unless it is void or self-closing. Comments and doctypes
+    /// are dropped, as is indentation between tags.
+    ///
+    /// ```
+    /// use blastview::node::Node;
+    ///
+    /// let node = Node::parse_html(r#"<ul class="menu"><li>Home</li><li>About</li></ul>"#)?;
+    /// assert!(matches!(node, Node::Element(_)));
+    ///
+    /// let error = Node::parse_html("<div>\n  <p>text</div>").unwrap_err();
+    /// assert_eq!(error.to_string(), "2:10: expected `</p>`, found `</div>`");
+    /// # Ok::<(), blastview::html::ParseError>(())
+    /// ```
+    pub fn parse_html(html: &str) -> Result<Node, ParseError> {
+        let mut nodes: Vec<Node> = tree::parse(html)?.into_iter().map(from_tree).collect();
+        match nodes.len() {
+            1 => Ok(nodes.pop().unwrap()),
+            _ => Ok(Node::fragment(nodes)),
+        }
+    }
+}
+
+fn from_tree(node: TreeNode) -> Node {
+    match node {
+        TreeNode::Text { text, .. } => Node::text(&text).into(),
+        TreeNode::Element {
+            tag,
+            attrs,
+            children,
+            ..
+        } => {
+            let mut element = ElementNode::new(&tag);
+            for attr in attrs {
+                element = match attr.value {
+                    Some(value) => element.attr(&attr.name, &value),
+                    None => element.flag(&attr.name, true),
+                };
+            }
+            element.children(children.into_iter().map(from_tree)).into()
+        }
+    }
 }
 
+impl<N: Into<Node>> FromIterator<N> for Node {
+    fn from_iter<I: IntoIterator<Item = N>>(nodes: I) -> Self {
+        Self::fragment(nodes)
+    }
+}
+
+// fragments never nest, and adjacent texts are merged as the browser parses them into a
+// single text node, so that nodes map one to one to the DOM
+fn push_flattened(children: &mut Vec<Node>, node: Node) {
+    match node {
+        Node::Fragment(fragment) => {
+            for node in fragment.0 {
+                push_flattened(children, node);
+            }
+        }
+        // empty text has no DOM node at all
+        Node::Text(text) if text.0.is_empty() => {}
+        Node::Text(text) => match children.last_mut() {
+            Some(Node::Text(last)) => last.0.push_str(&text.0),
+            _ => children.push(Node::Text(text)),
+        },
+        node => children.push(node),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FragmentNode(pub(crate) Vec<Node>);
+
 #[derive(Debug, Clone)]
 pub struct TextNode(pub(crate) String);
 
@@ -31,8 +129,9 @@ pub struct TextNode(pub(crate) String);
 pub struct ElementNode {
     pub(crate) id: Uuid,
     pub(crate) tag: String,
-    pub(crate) attrs: HashMap<String, String>,
-    pub(crate) events: HashMap<String, Arc<dyn Fn() + Send + Sync>>,
+    pub(crate) attrs: BTreeMap<String, String>,
+    pub(crate) bindings: BTreeMap<String, Arc<dyn Bindable>>,
+    pub(crate) events: BTreeMap<String, EventHandler>,
     pub(crate) children: Vec<Node>,
 }
 
@@ -42,6 +141,7 @@ impl Debug for ElementNode {
             .field("id", &self.id)
             .field("tag", &self.tag)
             .field("attrs", &self.attrs)
+            .field("bindings", &self.bindings.keys().collect::<Vec<_>>())
             .field(
                 "events",
                 &self.events.iter().map(|(name, _)| name).collect::<Vec<_>>(),
@@ -57,30 +157,114 @@ impl ElementNode {
             id: Uuid::new_v4(),
             tag: tag.to_string(),
             attrs: Default::default(),
+            bindings: Default::default(),
             events: Default::default(),
             children: Default::default(),
         }
     }
 
     pub fn attr(mut self, attr: &str, val: &str) -> Self {
+        self.bindings.remove(attr);
         self.attrs.insert(attr.to_string(), val.to_string());
         self
     }
 
+    /// Sets a boolean attribute such as `disabled`, which is either present without a value or absent.
+    pub fn flag(mut self, attr: &str, on: bool) -> Self {
+        self.bindings.remove(attr);
+        if on {
+            self.attrs.insert(attr.to_string(), String::new());
+        } else {
+            self.attrs.remove(attr);
+        }
+        self
+    }
+
+    /// Binds an attribute to a signal, so that it is patched whenever the signal changes.
+    pub fn bind<T>(mut self, attr: &str, signal: Signal<T>) -> Self
+    where
+        T: Display + Send + Sync + 'static,
+    {
+        self.attrs.remove(attr);
+        self.bindings.insert(attr.to_string(), Arc::new(signal));
+        self
+    }
+
     pub fn on<F>(mut self, event: &str, handler: F) -> Self
     where
         F: Fn() + Send + Sync + 'static,
     {
-        self.events.insert(event.to_string(), Arc::new(handler));
+        self.events
+            .insert(event.to_string(), EventHandler::Sync(Arc::new(handler)));
+        self
+    }
+
+    /// Attaches an asynchronous event handler.
+    ///
+    /// The handler runs as a task that is cancelled when the view is dropped.
+    /// While it runs, the element carries a `data-pending` attribute on the client.
+    pub fn on_async<F, Fut>(mut self, event: &str, handler: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.events.insert(
+            event.to_string(),
+            EventHandler::Async(Arc::new(move || Box::pin(handler()))),
+        );
         self
     }
 
     pub fn child(mut self, node: impl Into<Node>) -> Self {
-        self.children.push(node.into());
+        push_flattened(&mut self.children, node.into());
+        self
+    }
+
+    /// Appends several children, such as the items of a list or an `Option`.
+    pub fn children<N: Into<Node>>(mut self, nodes: impl IntoIterator<Item = N>) -> Self {
+        for node in nodes {
+            push_flattened(&mut self.children, node.into());
+        }
         self
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct RawHtmlNode {
+    pub(crate) id: Uuid,
+    pub(crate) html: Trusted,
+}
+
+#[derive(Clone)]
+pub struct SignalNode {
+    pub(crate) id: Uuid,
+    pub(crate) signal: Arc<dyn Bindable>,
+}
+
+impl Debug for SignalNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignalNode")
+            .field("id", &self.id)
+            .field("signal", &self.signal.id())
+            .finish()
+    }
+}
+
+impl<T: Display + Send + Sync + 'static> From<Signal<T>> for SignalNode {
+    fn from(signal: Signal<T>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            signal: Arc::new(signal),
+        }
+    }
+}
+
+impl<T: Display + Send + Sync + 'static> From<Signal<T>> for Node {
+    fn from(signal: Signal<T>) -> Self {
+        Self::Signal(Box::new(signal.into()))
+    }
+}
+
 impl<T: Display> From<T> for TextNode {
     fn from(value: T) -> Self {
         Self(value.to_string())
@@ -105,6 +289,15 @@ impl Into<Node> for ElementNode {
     }
 }
 
+impl From<Trusted> for Node {
+    fn from(html: Trusted) -> Self {
+        Self::RawHtml(Box::new(RawHtmlNode {
+            id: Uuid::new_v4(),
+            html,
+        }))
+    }
+}
+
 impl Into<Node> for ViewRef {
     fn into(self) -> Node {
         Node::ViewRef(Box::new(self))
@@ -114,8 +307,9 @@ impl Into<Node> for ViewRef {
 pub trait RenderableElement {
     fn id(&self) -> Uuid;
     fn tag(&self) -> &str;
-    fn attrs(&self) -> &HashMap<String, String>;
-    fn events(&self) -> &HashMap<String, Arc<dyn Fn() + Send + Sync>>;
+    fn attrs(&self) -> &BTreeMap<String, String>;
+    fn bindings(&self) -> &BTreeMap<String, Arc<dyn Bindable>>;
+    fn events(&self) -> &BTreeMap<String, EventHandler>;
     fn children(&self) -> &[Node];
 }
 
@@ -128,11 +322,15 @@ impl RenderableElement for ElementNode {
         &self.tag
     }
 
-    fn attrs(&self) -> &HashMap<String, String> {
+    fn attrs(&self) -> &BTreeMap<String, String> {
         &self.attrs
     }
 
-    fn events(&self) -> &HashMap<String, Arc<dyn Fn() + Send + Sync>> {
+    fn bindings(&self) -> &BTreeMap<String, Arc<dyn Bindable>> {
+        &self.bindings
+    }
+
+    fn events(&self) -> &BTreeMap<String, EventHandler> {
         &self.events
     }
 
@@ -150,3 +348,43 @@ impl RenderableText for TextNode {
         &self.0
     }
 }
+
+pub trait RenderableSignal {
+    fn id(&self) -> Uuid;
+    fn text(&self) -> String;
+}
+
+impl RenderableSignal for SignalNode {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn text(&self) -> String {
+        self.signal.render()
+    }
+}
+
+pub trait RenderableRawHtml {
+    fn id(&self) -> Uuid;
+    fn html(&self) -> &str;
+}
+
+impl RenderableRawHtml for RawHtmlNode {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn html(&self) -> &str {
+        self.html.as_str()
+    }
+}
+
+pub trait RenderableFragment {
+    fn children(&self) -> &[Node];
+}
+
+impl RenderableFragment for FragmentNode {
+    fn children(&self) -> &[Node] {
+        &self.0
+    }
+}
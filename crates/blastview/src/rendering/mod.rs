@@ -1,24 +1,205 @@
-use std::{collections::HashSet, sync::Mutex};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
 
+use tokio::sync::Notify;
 use uuid::Uuid;
 
+use crate::{
+    context::{NodePatch, effects::PendingEffect, suspense::PendingResources},
+    signal::Bindable,
+};
+
 #[derive(Default)]
 pub struct RenderingQueue {
     pub render_queue: Mutex<HashSet<Uuid>>,
-    pub deferred_queue: Mutex<HashSet<Uuid>>,
+    pub signal_queue: Mutex<HashMap<SignalTarget, Arc<dyn Bindable>>>,
+    pub patch_queue: Mutex<Vec<NodePatch>>,
+    effect_queue: Mutex<Vec<PendingEffect>>,
+    // every resource still loading in the tree, whether it is below a suspense boundary or not
+    pub(crate) resources: PendingResources,
+    enqueued: Notify,
+}
+
+/// A node (or one of its attributes) bound to a signal.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct SignalTarget {
+    pub node_id: Uuid,
+    pub attr: Option<String>,
+}
+
+enum Pending {
+    View(Uuid),
+    Signal(SignalTarget, Arc<dyn Bindable>),
+}
+
+#[derive(Default)]
+struct Batch {
+    depth: usize,
+    pending: Vec<(Arc<RenderingQueue>, Pending)>,
+}
+
+thread_local! {
+    static BATCH: RefCell<Batch> = RefCell::default();
+}
+
+/// Runs `f`, deferring all re-renders it causes until it returns.
+///
+/// Every affected view is then re-rendered once, and the resulting patches
+/// are sent to the client together.
+pub fn batch<R>(f: impl FnOnce() -> R) -> R {
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            let pending = BATCH.with_borrow_mut(|batch| {
+                batch.depth -= 1;
+                if batch.depth == 0 {
+                    std::mem::take(&mut batch.pending)
+                } else {
+                    vec![]
+                }
+            });
+
+            let mut queues: Vec<(Arc<RenderingQueue>, Vec<Pending>)> = vec![];
+            for (queue, item) in pending {
+                match queues.iter_mut().find(|(q, _)| Arc::ptr_eq(q, &queue)) {
+                    Some((_, items)) => items.push(item),
+                    None => queues.push((queue, vec![item])),
+                }
+            }
+
+            for (queue, items) in queues {
+                queue.commit(items);
+            }
+        }
+    }
+
+    BATCH.with_borrow_mut(|batch| batch.depth += 1);
+    let _guard = Guard;
+    f()
 }
 
 impl RenderingQueue {
-    pub(crate) fn enqueue(&self, id: Uuid) {
-        if let Ok(mut queue) = self.render_queue.try_lock() {
-            queue.insert(id);
-        } else {
-            self.deferred_queue.lock().unwrap().insert(id);
+    pub(crate) fn enqueue(self: &Arc<Self>, id: Uuid) {
+        self.submit(Pending::View(id));
+    }
+
+    pub(crate) fn enqueue_signal(
+        self: &Arc<Self>,
+        target: SignalTarget,
+        signal: Arc<dyn Bindable>,
+    ) {
+        self.submit(Pending::Signal(target, signal));
+    }
+
+    fn submit(self: &Arc<Self>, item: Pending) {
+        let item = BATCH.with_borrow_mut(|batch| {
+            if batch.depth > 0 {
+                batch.pending.push((Arc::clone(self), item));
+                None
+            } else {
+                Some(item)
+            }
+        });
+
+        if let Some(item) = item {
+            self.commit(vec![item]);
+        }
+    }
+
+    fn commit(&self, items: Vec<Pending>) {
+        let mut views = vec![];
+        for item in items {
+            match item {
+                Pending::View(id) => views.push(id),
+                Pending::Signal(target, signal) => {
+                    self.signal_queue.lock().unwrap().insert(target, signal);
+                }
+            }
+        }
+
+        if !views.is_empty() {
+            self.render_queue.lock().unwrap().extend(views);
+            self.enqueued.notify_one();
         }
     }
 
+    /// Waits until a view is enqueued for re-rendering.
+    pub async fn enqueued(&self) {
+        self.enqueued.notified().await;
+    }
+
+    /// Returns true while any resource in the tree is loading.
+    pub fn has_pending_resources(&self) -> bool {
+        !self.resources.is_empty()
+    }
+
+    /// Takes the views to re-render, releasing the queue so that renders can enqueue more.
+    pub fn take_views(&self) -> HashSet<Uuid> {
+        std::mem::take(&mut *self.render_queue.lock().unwrap())
+    }
+
+    pub(crate) fn enqueue_effects(&self, effects: Vec<PendingEffect>) {
+        self.effect_queue.lock().unwrap().extend(effects);
+    }
+
+    /// Runs the effects of committed renders, in the order their views finished rendering.
+    ///
+    /// All cleanups of effects that are about to re-run happen before any effect runs.
+    pub fn flush_effects(&self) {
+        let effects = std::mem::take(&mut *self.effect_queue.lock().unwrap());
+
+        for effect in effects.iter() {
+            effect.cleanup();
+        }
+
+        for effect in effects {
+            effect.run();
+        }
+    }
+
+    /// Queues a patch that is sent to the client without re-rendering any view.
+    pub(crate) fn enqueue_patch(&self, patch: NodePatch) {
+        self.patch_queue.lock().unwrap().push(patch);
+    }
+
+    pub fn take_patches(&self) -> Vec<NodePatch> {
+        std::mem::take(&mut *self.patch_queue.lock().unwrap())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.render_queue.lock().unwrap().is_empty()
+            && self.signal_queue.lock().unwrap().is_empty()
+            && self.patch_queue.lock().unwrap().is_empty()
+    }
+
+    /// Drains changed signals into patches for the nodes bound to them.
+    pub fn take_signal_patches(&self) -> Vec<NodePatch> {
+        self.signal_queue
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(target, signal)| match target.attr {
+                Some(attr) => NodePatch::SetAttr {
+                    node_id: target.node_id,
+                    attr,
+                    value: signal.render(),
+                },
+                None => NodePatch::SetText {
+                    node_id: target.node_id,
+                    text: signal.render(),
+                },
+            })
+            .collect()
+    }
+
     pub fn clear(&self) {
         self.render_queue.lock().unwrap().clear();
-        self.deferred_queue.lock().unwrap().clear();
+        self.effect_queue.lock().unwrap().clear();
+        self.signal_queue.lock().unwrap().clear();
+        self.patch_queue.lock().unwrap().clear();
     }
 }
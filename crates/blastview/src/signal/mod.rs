@@ -0,0 +1,163 @@
+use std::{
+    fmt::Display,
+    sync::{Arc, Mutex, RwLock, Weak},
+};
+
+use uuid::Uuid;
+
+pub type Listener = Arc<dyn Fn() + Send + Sync>;
+
+/// A reactive value that can be placed directly into a node tree.
+///
+/// Nodes bound to a signal are patched in place when it changes,
+/// without re-rendering the view that produced them.
+pub struct Signal<T> {
+    inner: Arc<SignalInner<T>>,
+}
+
+struct SignalInner<T> {
+    id: Uuid,
+    value: RwLock<T>,
+    subscribers: Mutex<Vec<Weak<dyn Fn() + Send + Sync>>>,
+    // listeners that keep a derived signal in sync with its sources
+    sources: Mutex<Vec<Listener>>,
+}
+
+impl<T: Send + Sync + 'static> Signal<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Arc::new(SignalInner {
+                id: Uuid::new_v4(),
+                value: RwLock::new(value),
+                subscribers: Default::default(),
+                sources: Default::default(),
+            }),
+        }
+    }
+
+    /// Creates a signal that is recomputed whenever any of `sources` changes.
+    pub fn computed<F>(sources: &[&dyn Observable], f: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        let signal = Self::new(f());
+
+        let derived = Arc::downgrade(&signal.inner);
+        let listener: Listener = Arc::new(move || {
+            if let Some(derived) = derived.upgrade() {
+                derived.set(f());
+            }
+        });
+
+        for source in sources {
+            source.subscribe(&listener);
+        }
+        signal.inner.sources.lock().unwrap().push(listener);
+
+        signal
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.inner.id
+    }
+
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.inner.value.read().unwrap())
+    }
+
+    pub fn set(&self, value: T) {
+        self.inner.set(value);
+    }
+
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        f(&mut self.inner.value.write().unwrap());
+        self.inner.notify();
+    }
+
+    /// Creates a signal derived from this one.
+    ///
+    /// Called during render, this creates a new signal every time; a re-render only patches
+    /// the nodes bound to it when its value differs from the previous one.
+    pub fn map<U, F>(&self, f: F) -> Signal<U>
+    where
+        U: Send + Sync + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+    {
+        let source = self.clone();
+        Signal::computed(&[self], move || source.with(&f))
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Signal<T> {
+    pub fn get(&self) -> T {
+        self.with(T::clone)
+    }
+}
+
+impl<T> SignalInner<T> {
+    fn set(&self, value: T) {
+        *self.value.write().unwrap() = value;
+        self.notify();
+    }
+
+    fn notify(&self) {
+        // listeners are called without holding the lock, as they may subscribe to signals themselves
+        let listeners: Vec<_> = {
+            let mut subscribers = self.subscribers.lock().unwrap();
+            subscribers.retain(|listener| listener.strong_count() > 0);
+            subscribers.iter().filter_map(Weak::upgrade).collect()
+        };
+
+        for listener in listeners {
+            listener();
+        }
+    }
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+/// Two signals are equal if they share the same value cell.
+impl<T> PartialEq for Signal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+/// Anything that notifies listeners when it changes.
+///
+/// Listeners are held weakly: a subscription lasts as long as the caller keeps the listener alive.
+pub trait Observable: Send + Sync {
+    fn subscribe(&self, listener: &Listener);
+}
+
+impl<T: Send + Sync> Observable for Signal<T> {
+    fn subscribe(&self, listener: &Listener) {
+        self.inner
+            .subscribers
+            .lock()
+            .unwrap()
+            .push(Arc::downgrade(listener));
+    }
+}
+
+/// A type-erased signal that can be rendered into a node tree.
+pub trait Bindable: Observable {
+    fn id(&self) -> Uuid;
+    fn render(&self) -> String;
+}
+
+impl<T: Display + Send + Sync + 'static> Bindable for Signal<T> {
+    fn id(&self) -> Uuid {
+        self.inner.id
+    }
+
+    fn render(&self) -> String {
+        self.with(T::to_string)
+    }
+}
@@ -0,0 +1,444 @@
+//! HTML templates loaded at runtime, for markup edited outside of Rust.
+//!
+//! A template is plain HTML, with `{{ name }}` slots in text and attribute values, and
+//! `on:event="handler"` or `on_async:event="handler"` attributes naming event handlers:
+//!
+//! ```html
+//! <div class="counter {{ theme }}">
+//!   <p>Count: {{ count }}</p>
+//!   <button on:click="increment" disabled="{{ at_max }}">+1</button>
+//! </div>
+//! ```
+//!
+//! Slot values are escaped, so slots cannot appear inside `<script>` or `<style>`, where
+//! escaping would not keep them from running as code.
+//!
+//! A view fills the slots and handlers with [`Bindings`]:
+//!
+//! ```no_run
+//! use blastview::{
+//!     context::ViewContext,
+//!     node::Node,
+//!     template::{Bindings, TemplateError, Templates},
+//!     view::TryView,
+//! };
+//!
+//! struct Counter {
+//!     templates: Templates,
+//! }
+//!
+//! impl TryView for Counter {
+//!     type Error = TemplateError;
+//!
+//!     fn try_render(&self, cx: &impl ViewContext) -> Result<impl Into<Node>, TemplateError> {
+//!         let template = self.templates.use_template(cx, "counter.html")?;
+//!         let (count, set_count) = cx.use_state(0);
+//!         Ok(template.render(
+//!             &Bindings::new()
+//!                 .slot("theme", "dark")
+//!                 .slot("count", count)
+//!                 .flag("at_max", count >= 10)
+//!                 .on("increment", move || set_count(count + 1)),
+//!         ))
+//!     }
+//! }
+//! ```
+
+mod parser;
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    future::Future,
+    hash::{DefaultHasher, Hash, Hasher},
+    io,
+    path::PathBuf,
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+
+use tokio::sync::watch;
+
+use crate::{
+    context::{ViewContext, events::EventHandler},
+    node::{ElementNode, Node},
+};
+use parser::{Part, TemplateAttr, TemplateNode};
+
+/// A parsed template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    name: String,
+    roots: Vec<TemplateNode>,
+}
+
+impl Template {
+    /// Parses a template, `name` being used in errors.
+    pub fn parse(name: impl Into<String>, source: &str) -> Result<Self, TemplateError> {
+        let name = name.into();
+        match parser::parse(source) {
+            Ok(roots) => Ok(Self { name, roots }),
+            Err(error) => Err(TemplateError::Syntax {
+                template: name,
+                line: error.line(),
+                column: error.column(),
+                message: error.message().to_string(),
+            }),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Builds the node tree of the template, filling its slots and handlers from `bindings`.
+    ///
+    /// Slots without a value are left empty, and events without a handler are not attached.
+    /// Both are logged through `tracing`.
+    pub fn render(&self, bindings: &Bindings) -> Node {
+        let mut nodes = vec![];
+        for node in &self.roots {
+            self.render_node(node, bindings, &mut nodes);
+        }
+        match nodes.len() {
+            1 => nodes.pop().unwrap(),
+            _ => Node::fragment(nodes),
+        }
+    }
+
+    fn render_node(&self, node: &TemplateNode, bindings: &Bindings, output: &mut Vec<Node>) {
+        match node {
+            TemplateNode::Text(parts) => {
+                let mut text = String::new();
+                for part in parts {
+                    match part {
+                        Part::Static(value) => text.push_str(value),
+                        Part::Slot(name) => match self.slot(bindings, name) {
+                            Some(Slot::Node(node)) => {
+                                if !text.is_empty() {
+                                    output.push(std::mem::take(&mut text).into());
+                                }
+                                output.push(node.clone());
+                            }
+                            Some(slot) => text.push_str(&slot.to_text()),
+                            None => {}
+                        },
+                    }
+                }
+                if !text.is_empty() {
+                    output.push(text.into());
+                }
+            }
+            TemplateNode::Element {
+                tag,
+                attrs,
+                children,
+            } => {
+                let mut element = ElementNode::new(tag);
+                for attr in attrs {
+                    element = self.render_attr(element, attr, bindings);
+                }
+                let mut nodes = vec![];
+                for child in children {
+                    self.render_node(child, bindings, &mut nodes);
+                }
+                output.push(element.children(nodes).into());
+            }
+        }
+    }
+
+    fn render_attr(
+        &self,
+        element: ElementNode,
+        attr: &TemplateAttr,
+        bindings: &Bindings,
+    ) -> ElementNode {
+        match attr {
+            TemplateAttr::Flag(name) => element.flag(name, true),
+            // a value made of a single flag slot sets a boolean attribute
+            TemplateAttr::Value(name, parts) => match &parts[..] {
+                [Part::Slot(slot)] if matches!(bindings.slots.get(slot), Some(Slot::Flag(_))) => {
+                    element.flag(name, matches!(bindings.slots[slot], Slot::Flag(true)))
+                }
+                parts => {
+                    let value: String = parts
+                        .iter()
+                        .map(|part| match part {
+                            Part::Static(value) => value.clone(),
+                            Part::Slot(slot) => self
+                                .slot(bindings, slot)
+                                .map(Slot::to_text)
+                                .unwrap_or_default(),
+                        })
+                        .collect();
+                    element.attr(name, &value)
+                }
+            },
+            TemplateAttr::Event {
+                event,
+                handler,
+                is_async,
+            } => {
+                let Some(found) = bindings.handlers.get(handler) else {
+                    tracing::warn!(template = self.name, "no handler named `{handler}`");
+                    return element;
+                };
+                if matches!(found, EventHandler::Async(_)) != *is_async {
+                    let (bound, registered) = match is_async {
+                        true => ("on_async", "on"),
+                        false => ("on", "on_async"),
+                    };
+                    tracing::warn!(
+                        template = self.name,
+                        "handler `{handler}` is bound with `{bound}:` but registered with `{registered}`",
+                    );
+                }
+                let mut element = element;
+                element.events.insert(event.clone(), found.clone());
+                element
+            }
+        }
+    }
+
+    fn slot<'a>(&self, bindings: &'a Bindings, name: &str) -> Option<&'a Slot> {
+        let slot = bindings.slots.get(name);
+        if slot.is_none() {
+            tracing::warn!(template = self.name, "no value for slot `{name}`");
+        }
+        slot
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Slot {
+    Text(String),
+    Flag(bool),
+    Node(Node),
+}
+
+impl Slot {
+    fn to_text(&self) -> String {
+        match self {
+            Slot::Text(text) => text.clone(),
+            Slot::Flag(flag) => flag.to_string(),
+            Slot::Node(_) => String::new(),
+        }
+    }
+}
+
+/// The values of a template's slots, and its event handlers, by name.
+#[derive(Clone, Default)]
+pub struct Bindings {
+    slots: HashMap<String, Slot>,
+    handlers: HashMap<String, EventHandler>,
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fills a slot with text, escaped when rendered.
+    pub fn slot(mut self, name: &str, value: impl Display) -> Self {
+        self.slots
+            .insert(name.to_string(), Slot::Text(value.to_string()));
+        self
+    }
+
+    /// Fills a slot with a boolean, which sets or removes an attribute whose value is only
+    /// this slot, such as `disabled="{{ busy }}"`.
+    pub fn flag(mut self, name: &str, value: bool) -> Self {
+        self.slots.insert(name.to_string(), Slot::Flag(value));
+        self
+    }
+
+    /// Fills a text slot with a node, such as a child view.
+    pub fn node(mut self, name: &str, node: impl Into<Node>) -> Self {
+        self.slots.insert(name.to_string(), Slot::Node(node.into()));
+        self
+    }
+
+    pub fn on<F>(mut self, name: &str, handler: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.handlers
+            .insert(name.to_string(), EventHandler::Sync(Arc::new(handler)));
+        self
+    }
+
+    pub fn on_async<F, Fut>(mut self, name: &str, handler: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.handlers.insert(
+            name.to_string(),
+            EventHandler::Async(Arc::new(move || Box::pin(handler()))),
+        );
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// The template file could not be read.
+    Io { template: String, message: String },
+    /// The template is malformed.
+    Syntax {
+        template: String,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::Io { template, message } => {
+                write!(f, "template `{template}` could not be read: {message}")
+            }
+            TemplateError::Syntax {
+                template,
+                line,
+                column,
+                message,
+            } => write!(f, "template `{template}` at {line}:{column}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+type Loaded = Result<Arc<Template>, TemplateError>;
+
+struct Entry {
+    // a hash of the source, since edits within the same modification time are common
+    version: Option<u64>,
+    sender: watch::Sender<Loaded>,
+}
+
+struct TemplatesInner {
+    dir: PathBuf,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+/// Templates loaded from a directory, by their path relative to it.
+///
+/// Each template is read on first use, and kept until [`Templates::reload`] finds its source
+/// changed on disk. Views rendering a template through [`Templates::use_template`] re-render whenever
+/// it is reloaded.
+#[derive(Clone)]
+pub struct Templates {
+    inner: Arc<TemplatesInner>,
+}
+
+impl Templates {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            inner: Arc::new(TemplatesInner {
+                dir: dir.into(),
+                entries: Default::default(),
+            }),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Loaded {
+        self.watch(name).borrow().clone()
+    }
+
+    /// Returns a receiver that sees every reload of a template.
+    pub fn watch(&self, name: &str) -> watch::Receiver<Loaded> {
+        let mut entries = self.inner.entries.lock().unwrap();
+        let entry = entries.entry(name.to_string()).or_insert_with(|| {
+            let source = std::fs::read_to_string(self.inner.dir.join(name));
+            Entry {
+                version: version(&source),
+                sender: watch::Sender::new(load(name, source)),
+            }
+        });
+        entry.sender.subscribe()
+    }
+
+    /// Returns a template, and re-renders the view whenever the template is reloaded.
+    pub fn use_template(&self, cx: &impl ViewContext, name: &str) -> Loaded {
+        cx.use_watch(self.watch(name))
+    }
+
+    /// Reads again the templates changed on disk since they were loaded, and returns their names.
+    ///
+    /// Files are read and parsed without locking the templates, so that views can keep using them.
+    /// A template is only published if its source still differs from the last one recorded.
+    pub fn reload(&self) -> Vec<String> {
+        let names: Vec<String> = self.inner.entries.lock().unwrap().keys().cloned().collect();
+        let mut reloaded = vec![];
+        for name in names {
+            let source = std::fs::read_to_string(self.inner.dir.join(&name));
+            let version = version(&source);
+            let unchanged = |entry: &Entry| entry.version == version;
+            if self
+                .inner
+                .entries
+                .lock()
+                .unwrap()
+                .get(&name)
+                .is_none_or(unchanged)
+            {
+                continue;
+            }
+            let loaded = load(&name, source);
+            // the version and the template are published together, so that a concurrent
+            // reload cannot leave an older template in place of the version it recorded
+            let mut entries = self.inner.entries.lock().unwrap();
+            match entries.get_mut(&name) {
+                Some(entry) if !unchanged(entry) => {
+                    entry.version = version;
+                    entry.sender.send_modify(|current| *current = loaded);
+                }
+                _ => continue,
+            }
+            reloaded.push(name);
+        }
+        reloaded
+    }
+
+    /// Checks for changed templates every `period`, for as long as the templates are used.
+    ///
+    /// Meant for development, so that edited templates show up in every live session
+    /// without restarting the server. Must be called from within a Tokio runtime.
+    pub fn hot_reload(&self, period: Duration) -> Self {
+        let templates = Arc::downgrade(&self.inner);
+        tokio::spawn(watch_changes(templates, period));
+        self.clone()
+    }
+}
+
+async fn watch_changes(templates: Weak<TemplatesInner>, period: Duration) {
+    let mut interval = tokio::time::interval(period);
+    loop {
+        interval.tick().await;
+        let Some(inner) = templates.upgrade() else {
+            return;
+        };
+        for name in (Templates { inner }).reload() {
+            tracing::info!("template `{name}` reloaded");
+        }
+    }
+}
+
+/// Identifies the source of a template, or `None` if it could not be read.
+fn version(source: &io::Result<String>) -> Option<u64> {
+    let source = source.as_ref().ok()?;
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+fn load(name: &str, source: io::Result<String>) -> Loaded {
+    let source = source.map_err(|error| TemplateError::Io {
+        template: name.to_string(),
+        message: error.to_string(),
+    })?;
+    Template::parse(name, &source).map(Arc::new)
+}
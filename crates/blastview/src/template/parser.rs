@@ -0,0 +1,161 @@
+use crate::{
+    html::{
+        ParseError,
+        tokenizer::{Attribute, Token},
+        tree::{self, TreeNode},
+    },
+    node::ElementKind,
+};
+
+/// A piece of text or of an attribute value.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Part {
+    Static(String),
+    /// `{{ name }}`
+    Slot(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TemplateAttr {
+    Value(String, Vec<Part>),
+    /// An attribute written without a value.
+    Flag(String),
+    /// `on:event="handler"`, or `on_async:event="handler"`.
+    Event {
+        event: String,
+        handler: String,
+        is_async: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TemplateNode {
+    Text(Vec<Part>),
+    Element {
+        tag: String,
+        attrs: Vec<TemplateAttr>,
+        children: Vec<TemplateNode>,
+    },
+}
+
+/// Parses a template into its root nodes.
+pub(crate) fn parse(source: &str) -> Result<Vec<TemplateNode>, ParseError> {
+    // attributes and slots are checked as they are read, so that the first error in the
+    // document is reported, whether it is a template or a syntax error
+    let mut raw_text = None;
+    tree::parse_checked(source, |offset, token| match token {
+        Token::StartTag {
+            name,
+            attrs,
+            self_closing,
+        } => {
+            raw_text = (!self_closing && ElementKind::of(name) == ElementKind::RawText)
+                .then(|| name.clone());
+            attrs
+                .iter()
+                .try_for_each(|attr| parse_attr(source, offset, attr).map(drop))
+        }
+        // slots are escaped as HTML text, which does not keep them from running as code
+        Token::Text(text) if raw_text.is_some() => match text.find("{{") {
+            Some(at) => Err(ParseError::new(
+                source,
+                offset + at,
+                format!(
+                    "slots are not allowed inside `<{}>`",
+                    raw_text.as_ref().unwrap()
+                ),
+            )),
+            None => Ok(()),
+        },
+        Token::Text(text) => parse_parts(source, offset, text).map(drop),
+        Token::EndTag { .. } => {
+            raw_text = None;
+            Ok(())
+        }
+        Token::Comment(_) | Token::Doctype(_) => Ok(()),
+    })?
+    .into_iter()
+    .map(|node| template_node(source, node))
+    .collect()
+}
+
+fn template_node(source: &str, node: TreeNode) -> Result<TemplateNode, ParseError> {
+    match node {
+        TreeNode::Text { offset, text } => {
+            Ok(TemplateNode::Text(parse_parts(source, offset, &text)?))
+        }
+        TreeNode::Element {
+            offset,
+            tag,
+            attrs,
+            children,
+        } => Ok(TemplateNode::Element {
+            tag,
+            attrs: attrs
+                .into_iter()
+                .map(|attr| parse_attr(source, offset, &attr))
+                .collect::<Result<_, _>>()?,
+            children: children
+                .into_iter()
+                .map(|child| template_node(source, child))
+                .collect::<Result<_, _>>()?,
+        }),
+    }
+}
+
+fn parse_attr(source: &str, offset: usize, attr: &Attribute) -> Result<TemplateAttr, ParseError> {
+    let event = match attr.name.split_once(':') {
+        Some(("on", event)) => Some((event, false)),
+        Some(("on_async", event)) => Some((event, true)),
+        _ => None,
+    };
+
+    match (event, &attr.value) {
+        (Some((event, is_async)), Some(handler)) => Ok(TemplateAttr::Event {
+            event: event.to_string(),
+            handler: handler.trim().to_string(),
+            is_async,
+        }),
+        (Some(_), None) => Err(ParseError::new(
+            source,
+            offset,
+            format!("`{}` needs the name of a handler", attr.name),
+        )),
+        (None, Some(value)) => Ok(TemplateAttr::Value(
+            attr.name.clone(),
+            parse_parts(source, offset, value)?,
+        )),
+        (None, None) => Ok(TemplateAttr::Flag(attr.name.clone())),
+    }
+}
+
+/// Splits text into static parts and `{{ slot }}` parts.
+fn parse_parts(source: &str, offset: usize, mut text: &str) -> Result<Vec<Part>, ParseError> {
+    let mut parts = vec![];
+    while let Some(start) = text.find("{{") {
+        if start > 0 {
+            parts.push(Part::Static(text[..start].to_string()));
+        }
+        let Some(end) = text[start..].find("}}") else {
+            return Err(ParseError::new(source, offset, "unterminated `{{`"));
+        };
+        let name = text[start + 2..start + end].trim();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        {
+            return Err(ParseError::new(
+                source,
+                offset,
+                format!("invalid slot name `{name}`"),
+            ));
+        }
+        parts.push(Part::Slot(name.to_string()));
+        text = &text[start + end + 2..];
+    }
+    if !text.is_empty() {
+        parts.push(Part::Static(text.to_string()));
+    }
+    Ok(parts)
+}
@@ -0,0 +1,42 @@
+use crate::{
+    context::{Boundary, ViewContext},
+    node::Node,
+    view::{RenderableView, View, ViewError},
+};
+
+/// Renders a child view, or a fallback once the child or any of its descendants fails.
+///
+/// A view fails when its render returns an error (see [`crate::view::TryView`]),
+/// or when it panics while rendering, or in one of its event handlers, effects or tasks.
+/// The error is logged through `tracing`, and the rest of the page stays live.
+/// The fallback can call [`Boundary::reset`] to create the child view anew.
+pub struct ErrorBoundary<C, F> {
+    child: C,
+    fallback: F,
+}
+
+impl<V, C, F> ErrorBoundary<C, F>
+where
+    C: Fn() -> V,
+    F: Fn(&ViewError, &Boundary) -> Node,
+{
+    pub fn new(child: C, fallback: F) -> Self {
+        Self { child, fallback }
+    }
+}
+
+impl<V, C, F> View for ErrorBoundary<C, F>
+where
+    V: RenderableView + Send + Sync + 'static,
+    C: Fn() -> V + Send + Sync,
+    F: Fn(&ViewError, &Boundary) -> Node + Send + Sync,
+{
+    fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+        let boundary = cx.use_error_boundary();
+
+        match boundary.error() {
+            Some(error) => (self.fallback)(&error, &boundary),
+            None => cx.create_view(&self.child).into(),
+        }
+    }
+}
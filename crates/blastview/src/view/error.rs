@@ -0,0 +1,46 @@
+use std::{any::Any, fmt::Display};
+
+/// An error raised while rendering a view, or while running one of its event handlers or effects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewError {
+    view: &'static str,
+    message: String,
+}
+
+impl ViewError {
+    pub fn new(view: &'static str, message: impl Display) -> Self {
+        Self {
+            view,
+            message: message.to_string(),
+        }
+    }
+
+    pub(crate) fn from_panic(view: &'static str, payload: Box<dyn Any + Send>) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "panicked".to_string()
+        };
+
+        Self { view, message }
+    }
+
+    /// The type name of the view that failed.
+    pub fn view(&self) -> &'static str {
+        self.view
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ViewError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "view `{}` failed: {}", self.view, self.message)
+    }
+}
+
+impl std::error::Error for ViewError {}
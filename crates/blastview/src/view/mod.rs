@@ -1,19 +1,58 @@
+mod boundary;
+mod error;
+mod props;
+mod suspense;
+
+use std::{convert::Infallible, fmt::Display};
+
 use crate::{
     context::{Context, ViewContext},
     node::Node,
 };
+pub use blastview_macros::Props;
+pub use boundary::ErrorBoundary;
+pub use error::ViewError;
+pub use props::Props;
+pub use suspense::Suspense;
 
 pub trait RenderableView {
-    fn render(&self, cx: &Context) -> Node;
+    fn render(&self, cx: &Context) -> Result<Node, ViewError>;
+
+    /// The type name of the view, used in diagnostics.
+    fn name(&self) -> &'static str;
 }
 
 pub trait View: RenderableView {
     fn render(&self, cx: &impl ViewContext) -> impl Into<Node>;
 }
 
-impl<V: View> RenderableView for V {
-    fn render(&self, cx: &Context) -> Node {
-        <V as View>::render(&self, cx).into()
+/// A view whose render can fail.
+///
+/// The error is reported to the nearest error boundary, as if the view had panicked.
+pub trait TryView {
+    type Error: Display;
+
+    fn try_render(&self, cx: &impl ViewContext) -> Result<impl Into<Node>, Self::Error>;
+}
+
+impl<V: View> TryView for V {
+    type Error = Infallible;
+
+    fn try_render(&self, cx: &impl ViewContext) -> Result<impl Into<Node>, Self::Error> {
+        Ok(<V as View>::render(self, cx))
+    }
+}
+
+impl<V: TryView> RenderableView for V {
+    fn render(&self, cx: &Context) -> Result<Node, ViewError> {
+        match self.try_render(cx) {
+            Ok(node) => Ok(node.into()),
+            Err(error) => Err(ViewError::new(std::any::type_name::<V>(), error)),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        std::any::type_name::<V>()
     }
 }
 
@@ -0,0 +1,40 @@
+/// The fields a view is created from, set through a builder.
+///
+/// Derived with `#[derive(Props)]`, or generated by [`component`](crate::component). Fields
+/// marked `#[prop(default)]` or `#[prop(default = expr)]` may be left out, and
+/// `#[prop(optional)]` fields of type `Option<T>` are set from a `T`. The builder's `build`
+/// is only available once every other field is set.
+///
+/// ```
+/// use blastview::view::Props;
+///
+/// #[derive(Props)]
+/// struct Avatar {
+///     name: String,
+///     #[prop(default = 48)]
+///     size: u32,
+///     #[prop(optional)]
+///     url: Option<String>,
+/// }
+///
+/// let avatar = Avatar::builder().name("Ada").url("/ada.png").build();
+/// assert_eq!(avatar.size, 48);
+/// assert_eq!(avatar.url.as_deref(), Some("/ada.png"));
+/// ```
+///
+/// ```compile_fail
+/// use blastview::view::Props;
+///
+/// #[derive(Props)]
+/// struct Avatar {
+///     name: String,
+/// }
+///
+/// // `name` is required
+/// let avatar = Avatar::builder().build();
+/// ```
+pub trait Props: Sized {
+    type Builder;
+
+    fn builder() -> Self::Builder;
+}
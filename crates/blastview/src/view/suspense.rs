@@ -0,0 +1,46 @@
+use crate::{
+    context::ViewContext,
+    node::Node,
+    view::{RenderableView, View},
+};
+
+/// Renders a fallback until every resource loading below a child view has settled.
+///
+/// The child view is rendered hidden in the meantime, so that its resources start loading,
+/// and it is revealed at once when the last one settles.
+pub struct Suspense<C, F> {
+    child: C,
+    fallback: F,
+}
+
+impl<V, C, F> Suspense<C, F>
+where
+    C: Fn() -> V,
+    F: Fn() -> Node,
+{
+    pub fn new(child: C, fallback: F) -> Self {
+        Self { child, fallback }
+    }
+}
+
+impl<V, C, F> View for Suspense<C, F>
+where
+    V: RenderableView + Send + Sync + 'static,
+    C: Fn() -> V + Send + Sync,
+    F: Fn() -> Node + Send + Sync,
+{
+    fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+        let suspense = cx.use_suspense_boundary();
+        let content = Node::new("div").child(cx.create_view(&self.child));
+        let fallback = Node::new("div").child((self.fallback)());
+
+        // both are always rendered, so that settling only toggles their visibility
+        let (content, fallback) = if suspense.is_pending() {
+            (content.flag("hidden", true), fallback)
+        } else {
+            (content, fallback.flag("hidden", true))
+        };
+
+        Node::new("bv-suspense").child(content).child(fallback)
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use blastview::{
+    context::{Boundary, Context, ViewContext, events::Event},
+    node::{Node, RenderableElement},
+    rendering::RenderingQueue,
+    view::{ErrorBoundary, TryView, View},
+};
+
+struct FaultyView {
+    fail: Arc<AtomicBool>,
+}
+
+impl View for FaultyView {
+    fn render(&self, _cx: &impl ViewContext) -> impl Into<Node> {
+        if self.fail.load(Ordering::Relaxed) {
+            panic!("render failed");
+        }
+        Node::new("button").on("click", || panic!("click failed"))
+    }
+}
+
+fn rendered_tag(cx: &Context) -> Option<String> {
+    cx.with_last_render(|node| match node {
+        Some(Node::Element(element)) => Some(element.tag().to_string()),
+        _ => None,
+    })
+}
+
+fn boundary(
+    fail: &Arc<AtomicBool>,
+    rendering_queue: &Arc<RenderingQueue>,
+) -> (Arc<Context>, Arc<Mutex<Option<Boundary>>>) {
+    let handle = Arc::new(Mutex::new(None));
+    let fail = Arc::clone(fail);
+    let fallback_handle = Arc::clone(&handle);
+    let context = Context::new(
+        Arc::new(ErrorBoundary::new(
+            move || FaultyView {
+                fail: Arc::clone(&fail),
+            },
+            move |error, boundary| {
+                *fallback_handle.lock().unwrap() = Some(boundary.clone());
+                Node::new("strong").child(error.message()).into()
+            },
+        )),
+        Default::default(),
+        Arc::clone(rendering_queue),
+    );
+    (context, handle)
+}
+
+#[test]
+fn render_panic_shows_fallback_until_reset() {
+    let fail = Arc::new(AtomicBool::new(true));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let (context, handle) = boundary(&fail, &rendering_queue);
+
+    context.force_render();
+    assert_eq!(rendered_tag(&context).as_deref(), Some("strong"));
+    assert!(context.get_child(0).is_none());
+
+    let boundary = handle.lock().unwrap().clone().unwrap();
+    assert_eq!(boundary.error().unwrap().message(), "render failed");
+
+    fail.store(false, Ordering::Relaxed);
+    rendering_queue.take_views();
+    boundary.reset();
+    assert!(rendering_queue.take_views().contains(&context.id));
+
+    context.force_render();
+    assert!(boundary.error().is_none());
+    assert_eq!(rendered_tag(&context), None);
+    assert_eq!(
+        rendered_tag(&context.get_child(0).unwrap()).as_deref(),
+        Some("button")
+    );
+}
+
+#[test]
+fn event_handler_panic_is_caught_by_boundary() {
+    let fail = Arc::new(AtomicBool::new(false));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let (context, _) = boundary(&fail, &rendering_queue);
+    context.force_render();
+
+    let button_id = context
+        .get_child(0)
+        .unwrap()
+        .with_last_render(|node| match node {
+            Some(Node::Element(element)) => element.id(),
+            _ => unreachable!(),
+        });
+    context.dispatch_event(&Event {
+        node_id: button_id,
+        event: "click".to_string(),
+    });
+
+    assert!(rendering_queue.take_views().contains(&context.id));
+    context.force_render();
+    assert_eq!(rendered_tag(&context).as_deref(), Some("strong"));
+}
+
+#[test]
+fn render_panic_without_boundary_keeps_last_render() {
+    let fail = Arc::new(AtomicBool::new(false));
+    let context = Context::new(
+        Arc::new(FaultyView {
+            fail: Arc::clone(&fail),
+        }),
+        Default::default(),
+        Default::default(),
+    );
+    context.force_render();
+
+    fail.store(true, Ordering::Relaxed);
+    assert!(context.force_render().is_empty());
+    assert_eq!(rendered_tag(&context).as_deref(), Some("button"));
+
+    fail.store(false, Ordering::Relaxed);
+    context.force_render();
+    assert_eq!(rendered_tag(&context).as_deref(), Some("button"));
+}
+
+#[test]
+fn try_view_error_is_caught_by_boundary() {
+    struct LoadingView;
+    impl TryView for LoadingView {
+        type Error = String;
+
+        fn try_render(&self, _: &impl ViewContext) -> Result<impl Into<Node>, Self::Error> {
+            Err::<Node, _>("record not found".to_string())
+        }
+    }
+
+    let context = Context::new(
+        Arc::new(ErrorBoundary::new(
+            || LoadingView,
+            |error, _| Node::new("strong").child(error).into(),
+        )),
+        Default::default(),
+        Default::default(),
+    );
+    context.force_render();
+
+    assert_eq!(rendered_tag(&context).as_deref(), Some("strong"));
+}
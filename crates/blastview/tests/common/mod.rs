@@ -0,0 +1,31 @@
+use blastview::node::{Node, RenderableElement, RenderableFragment, RenderableText};
+
+/// Describes a node as markup, with its events but without ids nor bindings.
+pub fn describe(node: &Node) -> String {
+    match node {
+        Node::Element(element) => {
+            let attrs: String = element
+                .attrs()
+                .iter()
+                .map(|(attr, value)| format!(" {attr}={value:?}"))
+                .collect();
+            let events: String = element
+                .events()
+                .keys()
+                .map(|e| format!(" on:{e}"))
+                .collect();
+            let children: String = RenderableElement::children(element.as_ref())
+                .iter()
+                .map(describe)
+                .collect();
+            format!(
+                "<{tag}{attrs}{events}>{children}</{tag}>",
+                tag = element.tag()
+            )
+        }
+        Node::Text(text) => text.text().to_string(),
+        Node::Fragment(fragment) => fragment.children().iter().map(describe).collect(),
+        Node::ViewRef(_) => "<view>".to_string(),
+        _ => unreachable!(),
+    }
+}
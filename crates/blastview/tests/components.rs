@@ -0,0 +1,105 @@
+use std::{fmt::Display, sync::Arc};
+
+use blastview::{
+    component,
+    context::{Context, ViewContext},
+    html,
+    node::{Node, RenderableElement, RenderableText},
+    view,
+    view::{Props, View},
+};
+
+fn text(context: &Context) -> String {
+    context.with_last_render(|node| match node.unwrap() {
+        Node::Element(element) => RenderableElement::children(element.as_ref())
+            .iter()
+            .map(|child| match child {
+                Node::Text(text) => text.text().to_string(),
+                _ => unreachable!(),
+            })
+            .collect(),
+        _ => unreachable!(),
+    })
+}
+
+#[component]
+fn Badge(
+    _cx: &impl ViewContext,
+    label: String,
+    #[prop(default = 1)] count: u32,
+    #[prop(optional)] r#type: Option<String>,
+) -> Node {
+    let kind = r#type.unwrap_or_else(|| "info".to_string());
+    html! { <span>{format!("{kind} {label} {count}")}</span> }.into()
+}
+
+#[component]
+fn Pair<T: Display + Clone + Send + Sync + 'static>(
+    _cx: &impl ViewContext,
+    left: T,
+    right: T,
+) -> Node {
+    html! { <span>{format!("{left}/{right}")}</span> }.into()
+}
+
+struct App;
+
+impl View for App {
+    fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+        view!(cx,
+            <div>
+                <Badge label="plain" .. />
+                <Badge label="full" count={3u32} type="warning" .. />
+                <Pair::<i32> left={1} right={2} .. />
+            </div>
+        )
+    }
+}
+
+#[test]
+fn components_render_with_defaults_and_set_props() {
+    let context = Context::new(Arc::new(App), Default::default(), Default::default());
+    context.force_render();
+
+    let child = |order| text(&context.get_child(order).unwrap());
+    assert_eq!(child(0), "info plain 1");
+    assert_eq!(child(1), "warning full 3");
+    assert_eq!(child(2), "1/2");
+}
+
+#[test]
+fn component_props_are_public_fields() {
+    let badge = Badge::builder().label("new").r#type("error").build();
+    assert_eq!(badge.label, "new");
+    assert_eq!(badge.count, 1);
+    assert_eq!(badge.r#type.as_deref(), Some("error"));
+}
+
+#[test]
+fn derived_props_fall_back_to_defaults() {
+    #[derive(Props)]
+    struct Settings {
+        name: String,
+        #[prop(default)]
+        retries: u8,
+        #[prop(default = vec!["a".to_string()])]
+        tags: Vec<String>,
+        #[prop(optional)]
+        limit: Option<usize>,
+    }
+
+    let settings = Settings::builder().limit(3usize).name("fast").build();
+    assert_eq!(settings.name, "fast");
+    assert_eq!(settings.retries, 0);
+    assert_eq!(settings.tags, ["a"]);
+    assert_eq!(settings.limit, Some(3));
+
+    let settings = Settings::builder()
+        .name("slow")
+        .retries(5)
+        .tags(vec![])
+        .build();
+    assert_eq!(settings.retries, 5);
+    assert!(settings.tags.is_empty());
+    assert_eq!(settings.limit, None);
+}
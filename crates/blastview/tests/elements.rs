@@ -0,0 +1,125 @@
+use blastview::{
+    elements::*,
+    node::{Node, RenderableElement},
+};
+
+fn attrs(node: impl Into<Node>) -> Vec<(String, String)> {
+    match node.into() {
+        Node::Element(element) => element
+            .attrs()
+            .iter()
+            .map(|(attr, value)| (attr.to_string(), value.to_string()))
+            .collect(),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn typed_attributes_match_untyped_ones() {
+    let typed = input()
+        .r#type(InputType::DatetimeLocal)
+        .name("when")
+        .required(true)
+        .readonly(false)
+        .class("field")
+        .data("step", 15)
+        .aria("label", "When");
+
+    let untyped = Node::new("input")
+        .attr("type", "datetime-local")
+        .attr("name", "when")
+        .flag("required", true)
+        .flag("readonly", false)
+        .attr("class", "field")
+        .attr("data-step", "15")
+        .attr("aria-label", "When");
+
+    assert_eq!(attrs(typed), attrs(untyped));
+}
+
+#[test]
+fn renamed_attributes_use_their_html_name() {
+    let attrs = attrs(
+        label()
+            .r#for("email")
+            .dir(Direction::Rtl)
+            .child(a().r#type("text/html")),
+    );
+    assert_eq!(
+        attrs,
+        [
+            ("dir".to_string(), "rtl".to_string()),
+            ("for".to_string(), "email".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn events_are_registered_by_name() {
+    let node: Node = form()
+        .method(FormMethod::Post)
+        .on_submit(|| {})
+        .on_click_async(|| async {})
+        .into();
+
+    let Node::Element(element) = node else {
+        unreachable!()
+    };
+    let events: Vec<_> = element.events().keys().cloned().collect();
+    assert_eq!(events, ["click", "submit"]);
+}
+
+#[test]
+fn children_are_added_in_order() {
+    let node: Node = ul()
+        .child(li().child("first"))
+        .children(["second", "third"].map(|text| li().child(text)))
+        .into();
+
+    let Node::Element(element) = node else {
+        unreachable!()
+    };
+    assert_eq!(RenderableElement::children(element.as_ref()).len(), 3);
+}
+
+#[test]
+fn into_inner_exposes_the_element_node() {
+    let node = div().id("root").into_inner().attr("hx-boost", "true");
+    assert_eq!(
+        attrs(node),
+        [
+            ("hx-boost".to_string(), "true".to_string()),
+            ("id".to_string(), "root".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn document_elements_have_builders() {
+    let head = head()
+        .child(meta().charset("utf-8"))
+        .child(meta().http_equiv("refresh").content("30"))
+        .child(title().child("Dashboard"))
+        .child(base().href("/app/"))
+        .child(link().rel("stylesheet").href("/style.css"));
+    let document: Node = html().lang("en").child(head).child(body()).into();
+
+    let Node::Element(document) = document else {
+        unreachable!()
+    };
+    let Node::Element(head) = &RenderableElement::children(document.as_ref())[0] else {
+        unreachable!()
+    };
+    let tags: Vec<&str> = RenderableElement::children(head.as_ref())
+        .iter()
+        .map(|child| match child {
+            Node::Element(element) => RenderableElement::tag(element.as_ref()),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(tags, ["meta", "meta", "title", "base", "link"]);
+    assert_eq!(
+        attrs(meta().http_equiv("refresh")),
+        [("http-equiv".to_string(), "refresh".to_string())]
+    );
+}
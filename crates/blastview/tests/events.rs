@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use blastview::{
+    context::{Context, NodePatch, ViewContext, events::Event},
+    node::{Node, RenderableElement},
+    rendering::RenderingQueue,
+    view::View,
+};
+use tokio::sync::Notify;
+
+#[tokio::test]
+async fn async_handler_marks_element_pending() {
+    struct AsyncView {
+        release: Arc<Notify>,
+    }
+    impl View for AsyncView {
+        fn render(&self, _: &impl ViewContext) -> impl Into<Node> {
+            let release = Arc::clone(&self.release);
+            Node::new("button").on_async("click", move || {
+                let release = Arc::clone(&release);
+                async move { release.notified().await }
+            })
+        }
+    }
+
+    let release = Arc::new(Notify::new());
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(AsyncView {
+            release: Arc::clone(&release),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+    context.force_render();
+    let node_id = context.with_last_render(|node| match node {
+        Some(Node::Element(node)) => RenderableElement::id(node.as_ref()),
+        _ => unreachable!(),
+    });
+
+    context.dispatch_event(&Event {
+        node_id,
+        event: "click".to_string(),
+    });
+    assert!(matches!(
+        rendering_queue.take_patches().as_slice(),
+        [NodePatch::SetAttr { attr, .. }] if attr == "data-pending"
+    ));
+
+    release.notify_one();
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+    assert!(matches!(
+        rendering_queue.take_patches().as_slice(),
+        [NodePatch::RemoveAttr { attr, .. }] if attr == "data-pending"
+    ));
+}
@@ -0,0 +1,445 @@
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use tokio::sync::{broadcast, watch};
+
+use blastview::{
+    context::{Context, Ref, Resource, ViewContext},
+    node::Node,
+    rendering::RenderingQueue,
+    use_async_memo,
+    view::{ErrorBoundary, View, ViewError},
+};
+
+#[test]
+fn use_ref_persists_without_re_render() {
+    struct RefView {
+        handle: Arc<Mutex<Option<Ref<i32>>>>,
+    }
+    impl View for RefView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let counter = cx.use_ref(0);
+            *counter.lock() += 1;
+            *self.handle.lock().unwrap() = Some(counter.clone());
+            Node::new("div")
+        }
+    }
+
+    let handle = Arc::new(Mutex::new(None));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(RefView {
+            handle: Arc::clone(&handle),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+
+    context.force_render();
+    let first = handle.lock().unwrap().clone().unwrap();
+    context.force_render();
+    let second = handle.lock().unwrap().clone().unwrap();
+
+    assert!(first == second);
+    assert_eq!(second.get(), 2);
+
+    second.set(10);
+    assert_eq!(first.get(), 10);
+    assert!(rendering_queue.render_queue.lock().unwrap().is_empty());
+}
+
+#[test]
+fn state_without_partial_eq_re_renders() {
+    #[derive(Clone)]
+    struct Opaque(#[allow(dead_code)] Vec<u8>);
+
+    type Setters = (
+        Arc<dyn Fn(Opaque) + Send + Sync>,
+        Arc<dyn Fn(i32) + Send + Sync>,
+    );
+
+    struct OpaqueView {
+        setters: Arc<Mutex<Option<Setters>>>,
+    }
+    impl View for OpaqueView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let (_, set_opaque) = cx.use_state_always(Opaque(vec![]));
+            let (_, set_parity) = cx.use_state_with(0, |a: &i32, b: &i32| a % 2 == b % 2);
+            *self.setters.lock().unwrap() = Some((set_opaque, set_parity));
+            Node::new("div")
+        }
+    }
+
+    let setters = Arc::new(Mutex::new(None));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(OpaqueView {
+            setters: Arc::clone(&setters),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+    context.force_render();
+    let (set_opaque, set_parity) = setters.lock().unwrap().clone().unwrap();
+
+    set_parity(2);
+    assert!(rendering_queue.render_queue.lock().unwrap().is_empty());
+    set_parity(3);
+    assert!(
+        rendering_queue
+            .render_queue
+            .lock()
+            .unwrap()
+            .contains(&context.id)
+    );
+
+    rendering_queue.clear();
+    set_opaque(Opaque(vec![]));
+    assert!(
+        rendering_queue
+            .render_queue
+            .lock()
+            .unwrap()
+            .contains(&context.id)
+    );
+}
+
+#[tokio::test]
+async fn use_resource_settles_and_refetches() {
+    type Seen = Arc<Mutex<Vec<(Option<i32>, Option<String>, Option<i32>)>>>;
+
+    struct ResourceView {
+        seen: Seen,
+        resource: Arc<Mutex<Option<Resource<i32, String>>>>,
+    }
+    impl View for ResourceView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let fetches = cx.use_ref(0);
+            let resource = cx.use_resource((), move || async move {
+                let fetch = {
+                    let mut fetches = fetches.lock();
+                    *fetches += 1;
+                    *fetches
+                };
+                if fetch == 1 {
+                    Ok(42)
+                } else {
+                    Err("gone".to_string())
+                }
+            });
+            let (_, memo) = use_async_memo!(cx, async || 7);
+
+            self.seen.lock().unwrap().push((
+                resource.value().cloned(),
+                resource.error().cloned(),
+                memo,
+            ));
+            *self.resource.lock().unwrap() = Some(resource);
+            Node::new("div")
+        }
+    }
+
+    let seen: Seen = Default::default();
+    let resource = Arc::new(Mutex::new(None));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(ResourceView {
+            seen: Arc::clone(&seen),
+            resource: Arc::clone(&resource),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+
+    let settle = async || {
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        rendering_queue.clear();
+        context.force_render();
+        rendering_queue.flush_effects();
+    };
+
+    context.force_render();
+
+    rendering_queue.flush_effects();
+    settle().await;
+    assert_eq!(
+        seen.lock().unwrap().last(),
+        Some(&(Some(42), None, Some(7)))
+    );
+
+    resource.lock().unwrap().clone().unwrap().refetch();
+    context.force_render();
+    rendering_queue.flush_effects();
+    settle().await;
+    assert_eq!(
+        seen.lock().unwrap().last(),
+        Some(&(None, Some("gone".to_string()), Some(7)))
+    );
+}
+
+#[tokio::test]
+async fn use_broadcast_and_use_watch_keep_latest_item() {
+    type Seen = Arc<Mutex<Option<(Option<u32>, &'static str)>>>;
+
+    struct FeedView {
+        feed: broadcast::Sender<u32>,
+        status: watch::Receiver<&'static str>,
+        seen: Seen,
+    }
+    impl View for FeedView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let latest = cx.use_broadcast(self.feed.subscribe());
+            let status = cx.use_watch(self.status.clone());
+            *self.seen.lock().unwrap() = Some((latest, status));
+            Node::new("div")
+        }
+    }
+
+    let (feed, _) = broadcast::channel(4);
+    let (status_tx, status) = watch::channel("idle");
+    let seen = Arc::new(Mutex::new(None));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(FeedView {
+            feed: feed.clone(),
+            status,
+            seen: Arc::clone(&seen),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+    context.force_render();
+    rendering_queue.flush_effects();
+    assert_eq!(*seen.lock().unwrap(), Some((None, "idle")));
+
+    // let the subscriptions start before publishing
+    tokio::task::yield_now().await;
+    feed.send(1).unwrap();
+    feed.send(2).unwrap();
+    status_tx.send("live").unwrap();
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+
+    assert!(
+        rendering_queue
+            .render_queue
+            .lock()
+            .unwrap()
+            .contains(&context.id)
+    );
+    context.force_render();
+    rendering_queue.flush_effects();
+    assert_eq!(*seen.lock().unwrap(), Some((Some(2), "live")));
+}
+
+#[tokio::test(start_paused = true)]
+async fn timers_use_the_latest_callback() {
+    struct TimerView {
+        seen: Arc<Mutex<(i32, bool)>>,
+    }
+    impl View for TimerView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let (count, set_count) = cx.use_state(0);
+            let (fired, set_fired) = cx.use_state(false);
+            cx.use_interval(Duration::from_millis(10), move || set_count(count + 1));
+            cx.use_timeout(Duration::from_millis(25), move || set_fired(true));
+            *self.seen.lock().unwrap() = (count, fired);
+            Node::new("div")
+        }
+    }
+
+    let seen = Arc::new(Mutex::new((0, false)));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(TimerView {
+            seen: Arc::clone(&seen),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+    context.force_render();
+    rendering_queue.flush_effects();
+
+    for _ in 0..3 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        tokio::task::yield_now().await;
+        context.force_render();
+        rendering_queue.flush_effects();
+    }
+
+    assert_eq!(*seen.lock().unwrap(), (3, true));
+}
+
+#[tokio::test(start_paused = true)]
+async fn tasks_of_an_unmounted_child_are_aborted() {
+    struct Ticker {
+        ticks: Arc<AtomicUsize>,
+    }
+    impl View for Ticker {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let ticks = Arc::clone(&self.ticks);
+            cx.use_interval(Duration::from_millis(10), move || {
+                ticks.fetch_add(1, Ordering::SeqCst);
+            });
+            Node::new("span")
+        }
+    }
+
+    struct ParentView {
+        shown: Arc<AtomicBool>,
+        ticks: Arc<AtomicUsize>,
+    }
+    impl View for ParentView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let ticker = cx.create_view(|| Ticker {
+                ticks: Arc::clone(&self.ticks),
+            });
+            let node: Node = match self.shown.load(Ordering::SeqCst) {
+                true => ticker.into(),
+                false => Node::text("hidden").into(),
+            };
+            node
+        }
+    }
+
+    let shown = Arc::new(AtomicBool::new(true));
+    let ticks = Arc::new(AtomicUsize::new(0));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(ParentView {
+            shown: Arc::clone(&shown),
+            ticks: Arc::clone(&ticks),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+    context.force_render();
+    rendering_queue.flush_effects();
+
+    tokio::time::sleep(Duration::from_millis(25)).await;
+    assert_eq!(ticks.load(Ordering::SeqCst), 2);
+
+    shown.store(false, Ordering::SeqCst);
+    context.force_render();
+    rendering_queue.flush_effects();
+    assert!(context.get_child(0).is_none());
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(ticks.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_child_left_out_of_the_tree_is_created_again_on_every_render() {
+    struct Ticker {
+        ticks: Arc<AtomicUsize>,
+    }
+    impl View for Ticker {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let ticks = Arc::clone(&self.ticks);
+            cx.use_interval(Duration::from_millis(10), move || {
+                ticks.fetch_add(1, Ordering::SeqCst);
+            });
+            Node::new("span")
+        }
+    }
+
+    struct ParentView {
+        created: Arc<AtomicUsize>,
+        ticks: Arc<AtomicUsize>,
+    }
+    impl View for ParentView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            cx.create_view(|| {
+                self.created.fetch_add(1, Ordering::SeqCst);
+                Ticker {
+                    ticks: Arc::clone(&self.ticks),
+                }
+            });
+            Node::text("never placed")
+        }
+    }
+
+    let created = Arc::new(AtomicUsize::new(0));
+    let ticks = Arc::new(AtomicUsize::new(0));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(ParentView {
+            created: Arc::clone(&created),
+            ticks: Arc::clone(&ticks),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+    for _ in 0..3 {
+        context.force_render();
+        rendering_queue.flush_effects();
+        tokio::time::sleep(Duration::from_millis(25)).await;
+    }
+
+    assert_eq!(created.load(Ordering::SeqCst), 3);
+    assert_eq!(ticks.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn hook_order_violation_names_view_and_call_site() {
+    struct ConditionalView {
+        toggle: Arc<Mutex<bool>>,
+    }
+    impl View for ConditionalView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            if *self.toggle.lock().unwrap() {
+                cx.use_ref(0);
+            }
+            cx.use_state(String::new());
+            Node::new("div")
+        }
+    }
+
+    let toggle = Arc::new(Mutex::new(false));
+    let caught = Arc::new(Mutex::new(None));
+    let context = Context::new(
+        Arc::new(ErrorBoundary::new(
+            {
+                let toggle = Arc::clone(&toggle);
+                move || ConditionalView {
+                    toggle: Arc::clone(&toggle),
+                }
+            },
+            {
+                let caught = Arc::clone(&caught);
+                move |error: &ViewError, _| {
+                    *caught.lock().unwrap() = Some(error.clone());
+                    Node::new("div").into()
+                }
+            },
+        )),
+        Default::default(),
+        Default::default(),
+    );
+    context.force_render();
+
+    *toggle.lock().unwrap() = true;
+    context.get_child(0).unwrap().force_render();
+    context.force_render();
+    let error = caught.lock().unwrap().clone().unwrap();
+    let message = error.message();
+
+    assert!(message.contains("ConditionalView"), "{message}");
+    assert!(
+        message.contains("`use_ref::<blastview::context::refs::Ref<i32>>`"),
+        "{message}"
+    );
+    assert!(
+        message.contains("`use_state::<alloc::string::String>`"),
+        "{message}"
+    );
+    assert!(message.contains("tests/hooks.rs"), "{message}");
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use blastview::{
+    context::{Context, NodePatch, ViewContext},
+    html::{Sanitizer, Trusted},
+    node::Node,
+    view::View,
+};
+
+use common::describe;
+
+fn sanitize(html: &str) -> String {
+    Sanitizer::default().sanitize(html).as_str().to_string()
+}
+
+#[test]
+fn sanitizer_keeps_allowed_markup() {
+    assert_eq!(
+        sanitize(r#"<p>Hello, <a href="https://example.com" title=hi>world</a>!<br/></p>"#),
+        r#"<p>Hello, <a href="https://example.com" title="hi">world</a>!<br></p>"#
+    );
+}
+
+#[test]
+fn sanitizer_removes_scripts_and_handlers() {
+    assert_eq!(
+        sanitize(
+            r#"<p onclick="steal()">a<script>steal()</script><!-- x --><iframe>b</iframe></p>"#
+        ),
+        "<p>ab</p>"
+    );
+    assert_eq!(
+        sanitize(r#"<a href=" java&#x09;script:steal()">link</a>"#),
+        "<a>link</a>"
+    );
+}
+
+#[test]
+fn sanitizer_output_is_well_formed() {
+    assert_eq!(
+        sanitize("<ul><li>one<li>two</p>"),
+        "<ul><li>one<li>two</li></li></ul>"
+    );
+    assert_eq!(sanitize("</div>a &lt; b < c"), "a &lt; b &lt; c");
+    assert_eq!(sanitize(r#"<p title="unterminated>text"#), "");
+}
+
+#[test]
+fn sanitizer_keeps_the_first_of_duplicate_attributes() {
+    assert_eq!(
+        sanitize(r#"<b title=1 title=2>bold</b> and <a href="javascript:x()" href="/">link</a>"#),
+        r#"<b title="1">bold</b> and <a>link</a>"#
+    );
+}
+
+#[test]
+fn sanitizer_checks_every_url_attribute() {
+    let sanitizer = Sanitizer::default()
+        .allow_tags(["form", "button"])
+        .allow_attrs(["action", "formaction"]);
+    assert_eq!(
+        sanitizer
+            .sanitize(r#"<form action="/send"><button formaction="javascript:steal()">Send</button></form>"#)
+            .as_str(),
+        r#"<form action="/send"><button>Send</button></form>"#
+    );
+}
+
+#[test]
+fn sanitizer_allowlist_is_configurable() {
+    let sanitizer = Sanitizer::empty()
+        .allow_tags(["span"])
+        .allow_attrs(["class"]);
+    assert_eq!(
+        sanitizer
+            .sanitize(r#"<span class="x" id="y"><b>bold</b></span>"#)
+            .as_str(),
+        r#"<span class="x">bold</span>"#
+    );
+}
+
+#[test]
+fn raw_html_is_replaced_whole_when_it_changes() {
+    struct ArticleView {
+        html: Arc<Mutex<&'static str>>,
+    }
+    impl View for ArticleView {
+        fn render(&self, _: &impl ViewContext) -> impl Into<Node> {
+            Node::new("article").child(Trusted::assume_safe(*self.html.lock().unwrap()))
+        }
+    }
+
+    let html = Arc::new(Mutex::new("<p>first</p>"));
+    let context = Context::new(
+        Arc::new(ArticleView {
+            html: Arc::clone(&html),
+        }),
+        Default::default(),
+        Default::default(),
+    );
+    context.force_render();
+    assert!(context.force_render().is_empty());
+
+    *html.lock().unwrap() = "<p>second</p>";
+    let patches = context.force_render();
+    assert!(
+        matches!(
+            &patches[..],
+            [NodePatch::Replace {
+                node: Node::RawHtml(_),
+                ..
+            }]
+        ),
+        "{} patches",
+        patches.len()
+    );
+}
+
+#[test]
+fn parse_html_builds_the_same_tree_as_the_builders() {
+    let parsed = Node::parse_html(
+        r#"
+        <!doctype html>
+        <form method=post>
+          <!-- the email field -->
+          <label for="email">Email &amp; name</label>
+          <input id=email type="email" required>
+          <br/>
+          <script>if (a < b) {}</script>
+        </form>
+        "#,
+    )
+    .unwrap();
+
+    let built: Node = Node::new("form")
+        .attr("method", "post")
+        .child(
+            Node::new("label")
+                .attr("for", "email")
+                .child("Email & name"),
+        )
+        .child(
+            Node::new("input")
+                .attr("id", "email")
+                .attr("type", "email")
+                .flag("required", true),
+        )
+        .child(Node::new("br"))
+        .child(Node::new("script").child("if (a < b) {}"))
+        .into();
+
+    assert_eq!(describe(&parsed), describe(&built));
+}
+
+#[test]
+fn parse_html_keeps_inline_whitespace_and_several_roots() {
+    let parsed = Node::parse_html("<b>bold</b> <i>italic</i>\ntail").unwrap();
+    assert!(matches!(parsed, Node::Fragment(_)));
+    assert_eq!(describe(&parsed), "<b>bold</b> <i>italic</i>\ntail");
+}
+
+#[test]
+fn parse_html_reports_line_and_column() {
+    let error = |html: &str| {
+        let error = Node::parse_html(html).unwrap_err();
+        (error.line(), error.column(), error.message().to_string())
+    };
+
+    assert_eq!(
+        error("<ul>\n  <li>one</ul>"),
+        (2, 10, "expected `</li>`, found `</ul>`".to_string())
+    );
+    assert_eq!(
+        error("<p>text</p>\n</div>"),
+        (2, 1, "unexpected `</div>`".to_string())
+    );
+    assert_eq!(
+        error("<main>\n  <section>\n  </section>"),
+        (1, 1, "unclosed `<main>`".to_string())
+    );
+    assert_eq!(
+        error("<p>\n  é <a href=\"x\" href=\"y\">"),
+        (2, 17, "duplicate attribute".to_string())
+    );
+}
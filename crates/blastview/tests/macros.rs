@@ -0,0 +1,96 @@
+mod common;
+
+use std::sync::Arc;
+
+use blastview::{
+    context::{Context, ViewContext},
+    html,
+    node::Node,
+    view,
+    view::View,
+};
+
+use common::describe;
+
+#[test]
+fn html_expands_to_builder_calls() {
+    let items = ["one", "two"];
+    let node: Node = html! {
+        <div class="list" data-count={items.len()} hidden>
+            <ul>{..items.iter().map(|item| html! { <li>{item}</li> })}</ul>
+            <input type="checkbox" disabled={items.len() > 2}>
+            <button on:click={|| {}}>"Click" {1 + 1}</button>
+        </div>
+    }
+    .into();
+
+    let expected: Node = Node::new("div")
+        .attr("class", "list")
+        .attr("data-count", "2")
+        .flag("hidden", true)
+        .child(Node::new("ul").children(items.map(|item| Node::new("li").child(item))))
+        .child(
+            Node::new("input")
+                .attr("type", "checkbox")
+                .flag("disabled", false),
+        )
+        .child(
+            Node::new("button")
+                .child("Click")
+                .child(2)
+                .on("click", || {}),
+        )
+        .into();
+    assert_eq!(describe(&node), describe(&expected));
+}
+
+#[test]
+fn html_with_several_roots_is_a_fragment() {
+    let note: Option<&str> = None;
+    let node = html! {
+        <h1>"Title"</h1>
+        {..note}
+        <p>"Body"</p>
+    };
+    assert!(matches!(node, Node::Fragment(_)));
+    assert_eq!(describe(&node), "<h1>Title</h1><p>Body</p>");
+}
+
+#[test]
+fn view_creates_child_views() {
+    struct Greeting {
+        name: String,
+    }
+    impl View for Greeting {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            view!(cx, <p>{format!("Hello, {}!", self.name)}</p>)
+        }
+    }
+
+    struct App;
+    impl View for App {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            view!(cx,
+                <main>
+                    <Greeting name="world" />
+                    <Greeting name={String::from("you")} />
+                </main>
+            )
+        }
+    }
+
+    let context = Context::new(Arc::new(App), Default::default(), Default::default());
+    context.force_render();
+
+    assert_eq!(
+        context.with_last_render(|node| describe(node.unwrap())),
+        "<main><view><view></main>"
+    );
+    assert_eq!(
+        context
+            .get_child(1)
+            .unwrap()
+            .with_last_render(|node| describe(node.unwrap())),
+        "<p>Hello, you!</p>"
+    );
+}
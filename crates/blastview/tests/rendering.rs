@@ -0,0 +1,376 @@
+use std::sync::{Arc, Mutex};
+
+use blastview::{
+    batch,
+    context::{Context, NodePatch, ViewContext},
+    node::{Node, RenderableElement, RenderableFragment, RenderableText},
+    rendering::RenderingQueue,
+    view::View,
+};
+
+type Setter = Arc<dyn Fn(i32) + Send + Sync>;
+
+struct CounterView {
+    setter: Arc<Mutex<Option<Setter>>>,
+}
+
+impl View for CounterView {
+    fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+        let (count, set_count) = cx.use_state(0);
+        *self.setter.lock().unwrap() = Some(set_count);
+        Node::new("div").child(count)
+    }
+}
+
+fn counter(rendering_queue: &Arc<RenderingQueue>) -> (Arc<Context>, Setter) {
+    let setter = Arc::new(Mutex::new(None));
+    let context = Context::new(
+        Arc::new(CounterView {
+            setter: Arc::clone(&setter),
+        }),
+        Default::default(),
+        Arc::clone(rendering_queue),
+    );
+    context.force_render();
+    let setter = setter.lock().unwrap().clone().unwrap();
+    (context, setter)
+}
+
+#[test]
+fn batch_defers_re_renders_until_closure_returns() {
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let (a, set_a) = counter(&rendering_queue);
+    let (b, set_b) = counter(&rendering_queue);
+
+    batch(|| {
+        set_a(1);
+        batch(|| set_b(1));
+        assert!(rendering_queue.render_queue.lock().unwrap().is_empty());
+        set_a(2);
+    });
+
+    let queue = rendering_queue.render_queue.lock().unwrap();
+    assert_eq!(queue.len(), 2);
+    assert!(queue.contains(&a.id) && queue.contains(&b.id));
+}
+
+#[test]
+fn effects_run_after_render_child_first() {
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    fn logged_effect(cx: &impl ViewContext, log: &Log, name: &'static str, generation: usize) {
+        let log = Arc::clone(log);
+        cx.use_effect(
+            move || {
+                log.lock().unwrap().push(format!("run {name}"));
+                move || log.lock().unwrap().push(format!("cleanup {name}"))
+            },
+            generation,
+        );
+    }
+
+    struct ChildView {
+        log: Log,
+        generation: Arc<Mutex<usize>>,
+    }
+    impl View for ChildView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            logged_effect(cx, &self.log, "child", *self.generation.lock().unwrap());
+            Node::new("span")
+        }
+    }
+
+    struct ParentView {
+        log: Log,
+        generation: Arc<Mutex<usize>>,
+    }
+    impl View for ParentView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            logged_effect(cx, &self.log, "parent", *self.generation.lock().unwrap());
+            assert!(self.log.lock().unwrap().is_empty());
+            let child = cx.create_view(|| ChildView {
+                log: Arc::clone(&self.log),
+                generation: Arc::clone(&self.generation),
+            });
+            Node::new("div").child(child)
+        }
+    }
+
+    let log: Log = Default::default();
+    let generation = Arc::new(Mutex::new(0));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(ParentView {
+            log: Arc::clone(&log),
+            generation: Arc::clone(&generation),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+
+    context.force_render();
+    rendering_queue.flush_effects();
+    assert_eq!(*log.lock().unwrap(), ["run child", "run parent"]);
+
+    log.lock().unwrap().clear();
+    *generation.lock().unwrap() += 1;
+    context.force_render();
+    rendering_queue.flush_effects();
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["cleanup child", "cleanup parent", "run child", "run parent"]
+    );
+}
+
+#[test]
+fn effects_of_a_failed_render_run_on_the_next_render() {
+    struct FlakyView {
+        runs: Arc<Mutex<Vec<usize>>>,
+        generation: Arc<Mutex<(usize, bool)>>,
+    }
+    impl View for FlakyView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let (generation, fails) = *self.generation.lock().unwrap();
+            let runs = Arc::clone(&self.runs);
+            cx.use_effect(
+                move || {
+                    runs.lock().unwrap().push(generation);
+                    || {}
+                },
+                generation,
+            );
+            assert!(!fails, "render failed");
+            Node::new("div")
+        }
+    }
+
+    let runs = Arc::new(Mutex::new(vec![]));
+    let generation = Arc::new(Mutex::new((0, false)));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(FlakyView {
+            runs: Arc::clone(&runs),
+            generation: Arc::clone(&generation),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+    context.force_render();
+    rendering_queue.flush_effects();
+
+    *generation.lock().unwrap() = (1, true);
+    context.force_render();
+    rendering_queue.flush_effects();
+    assert_eq!(*runs.lock().unwrap(), [0]);
+
+    // the dependencies did not change since the failed render, but the effect never ran for them
+    *generation.lock().unwrap() = (1, false);
+    context.force_render();
+    rendering_queue.flush_effects();
+    assert_eq!(*runs.lock().unwrap(), [0, 1]);
+}
+
+#[test]
+fn effects_queued_by_several_renders_run_once_with_the_latest_deps() {
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    struct DepsView {
+        log: Log,
+        generation: Arc<Mutex<usize>>,
+    }
+    impl View for DepsView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let generation = *self.generation.lock().unwrap();
+            let log = Arc::clone(&self.log);
+            cx.use_effect(
+                move || {
+                    log.lock().unwrap().push(format!("run {generation}"));
+                    move || log.lock().unwrap().push(format!("cleanup {generation}"))
+                },
+                generation,
+            );
+            Node::new("div")
+        }
+    }
+
+    let log: Log = Default::default();
+    let generation = Arc::new(Mutex::new(0));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(DepsView {
+            log: Arc::clone(&log),
+            generation: Arc::clone(&generation),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+    let render_with = |deps: &[usize]| {
+        for deps in deps {
+            *generation.lock().unwrap() = *deps;
+            context.force_render();
+        }
+        rendering_queue.flush_effects();
+    };
+
+    render_with(&[0]);
+    // dependencies changing back before the effects are flushed cancel the queued run
+    render_with(&[1, 0]);
+    assert_eq!(*log.lock().unwrap(), ["run 0"]);
+
+    render_with(&[1, 2]);
+    render_with(&[3, 0, 3]);
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["run 0", "cleanup 0", "run 2", "cleanup 2", "run 3"]
+    );
+}
+
+#[test]
+fn concurrent_renders_of_a_view_do_not_interleave() {
+    struct SlowView {
+        renders: Arc<Mutex<usize>>,
+    }
+    impl View for SlowView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let (count, _) = cx.use_state(0u8);
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            let name = cx.use_ref("name").get();
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            let (flag, _) = cx.use_state(false);
+            *self.renders.lock().unwrap() += 1;
+            Node::new("div").child(format!("{count} {name} {flag}"))
+        }
+    }
+
+    let renders = Arc::new(Mutex::new(0));
+    let context = Context::new(
+        Arc::new(SlowView {
+            renders: Arc::clone(&renders),
+        }),
+        Default::default(),
+        Default::default(),
+    );
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..10 {
+                    context.force_render();
+                }
+            });
+        }
+    });
+
+    // renders are serialized, so none of them sees its hooks reset by another, and all count
+    assert_eq!(*renders.lock().unwrap(), 40);
+}
+
+struct ListView {
+    items: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl View for ListView {
+    fn render(&self, _: &impl ViewContext) -> impl Into<Node> {
+        let items = self.items.lock().unwrap().clone();
+        let footer = (items.len() > 2).then(|| Node::new("footer"));
+        let list = Node::new("ul").children(items.iter().map(|item| Node::new("li").child(*item)));
+        Node::fragment(std::iter::once(list).chain(footer))
+    }
+}
+
+#[test]
+fn fragments_are_flattened_and_lists_diffed_by_length() {
+    let items = Arc::new(Mutex::new(vec!["a", "b"]));
+    let context = Context::new(
+        Arc::new(ListView {
+            items: Arc::clone(&items),
+        }),
+        Default::default(),
+        Default::default(),
+    );
+    context.force_render();
+
+    let ul = |cx: &Context| {
+        cx.with_last_render(|node| {
+            let Some(Node::Fragment(fragment)) = node else {
+                panic!("expected a fragment root");
+            };
+            let Some(Node::Element(ul)) = RenderableFragment::children(fragment.as_ref()).first()
+            else {
+                unreachable!()
+            };
+            (ul.id(), RenderableElement::children(ul.as_ref()).len())
+        })
+    };
+    assert_eq!(ul(&context).1, 2);
+
+    items.lock().unwrap()[1] = "c";
+    let patches = context.force_render();
+    assert!(matches!(&patches[..], [NodePatch::ReplaceChild { .. }]));
+
+    // the footer appears, so the view's children are replaced
+    items.lock().unwrap().push("d");
+    let patches = context.force_render();
+    assert!(matches!(
+        &patches[..],
+        [NodePatch::ReplaceChildren { node_id, children }]
+            if *node_id == context.id && children.len() == 2
+    ));
+
+    let (ul_id, _) = ul(&context);
+    items.lock().unwrap().push("e");
+    let patches = context.force_render();
+    assert!(matches!(
+        &patches[..],
+        [NodePatch::ReplaceChildren { node_id, children }]
+            if *node_id == ul_id && children.len() == 4
+    ));
+}
+
+struct GreetingView {
+    name: Arc<Mutex<&'static str>>,
+}
+
+impl View for GreetingView {
+    fn render(&self, _: &impl ViewContext) -> impl Into<Node> {
+        let name = *self.name.lock().unwrap();
+        Node::new("p")
+            .children(["Hello, ", name])
+            .child(Node::new("br"))
+    }
+}
+
+#[test]
+fn adjacent_texts_are_patched_as_the_single_dom_node_they_render_to() {
+    let name = Arc::new(Mutex::new("Ada"));
+    let context = Context::new(
+        Arc::new(GreetingView {
+            name: Arc::clone(&name),
+        }),
+        Default::default(),
+        Default::default(),
+    );
+    context.force_render();
+    let p_id = context.with_last_render(|node| {
+        let Some(Node::Element(p)) = node else {
+            panic!("expected an element root");
+        };
+        assert_eq!(RenderableElement::children(p.as_ref()).len(), 2);
+        p.id()
+    });
+
+    *name.lock().unwrap() = "Grace";
+    let patches = context.force_render();
+    let [
+        NodePatch::ReplaceChild {
+            node_id,
+            child_idx: 0,
+            node: Node::Text(text),
+        },
+    ] = &patches[..]
+    else {
+        panic!("expected the first child to be replaced");
+    };
+    assert_eq!(*node_id, p_id);
+    assert_eq!(RenderableText::text(text.as_ref()), "Hello, Grace");
+}
@@ -0,0 +1,83 @@
+use std::sync::{Arc, Mutex};
+
+use blastview::{
+    context::{Context, NodePatch, ViewContext},
+    node::Node,
+    rendering::RenderingQueue,
+    signal::Signal,
+    view::View,
+};
+
+#[test]
+fn signal_change_patches_bound_nodes_only() {
+    struct ProgressView {
+        handle: Arc<Mutex<Option<Signal<u32>>>>,
+    }
+    impl View for ProgressView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let progress = cx.use_signal(0);
+            *self.handle.lock().unwrap() = Some(progress.clone());
+            Node::new("div")
+                .bind("style", progress.map(|p| format!("width: {p}%")))
+                .child(progress)
+        }
+    }
+
+    let handle = Arc::new(Mutex::new(None));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(ProgressView {
+            handle: Arc::clone(&handle),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+    context.force_render();
+
+    let progress = handle.lock().unwrap().clone().unwrap();
+    progress.set(10);
+    progress.set(42);
+
+    assert!(rendering_queue.render_queue.lock().unwrap().is_empty());
+
+    let mut patches = rendering_queue.take_signal_patches();
+    assert_eq!(patches.len(), 2);
+    patches.sort_by_key(|patch| matches!(patch, NodePatch::SetText { .. }));
+    assert!(matches!(
+        &patches[0],
+        NodePatch::SetAttr { attr, value, .. } if attr == "style" && value == "width: 42%"
+    ));
+    assert!(matches!(&patches[1], NodePatch::SetText { text, .. } if text == "42"));
+}
+
+#[test]
+fn signals_derived_during_render_are_not_patched_when_unchanged() {
+    struct LabelView {
+        count: Arc<Mutex<u32>>,
+    }
+    impl View for LabelView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let progress = cx.use_signal(7);
+            Node::new("div")
+                .bind("title", progress.map(|p| format!("{p}%")))
+                .child(progress.map(|p| p * 2))
+                .child(Node::new("span").child(self.count.lock().unwrap().to_string()))
+        }
+    }
+
+    let count = Arc::new(Mutex::new(0));
+    let context = Context::new(
+        Arc::new(LabelView {
+            count: Arc::clone(&count),
+        }),
+        Default::default(),
+        Default::default(),
+    );
+    context.force_render();
+
+    *count.lock().unwrap() = 1;
+    let patches = context.force_render();
+
+    assert_eq!(patches.len(), 1);
+    assert!(matches!(&patches[0], NodePatch::ReplaceChild { .. }));
+}
@@ -0,0 +1,73 @@
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::oneshot;
+
+use blastview::{
+    context::{Context, ViewContext, context_registry::ContextRegistry},
+    node::{Node, RenderableElement},
+    rendering::RenderingQueue,
+    view::{Suspense, View},
+};
+
+struct DataView {
+    receiver: Mutex<Option<oneshot::Receiver<i32>>>,
+}
+
+impl View for DataView {
+    fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+        let receiver = self.receiver.lock().unwrap().take();
+        let resource = cx.use_resource((), move || async move {
+            receiver.unwrap().await.map_err(|_| ())
+        });
+        Node::new("span").child(resource.value().copied().unwrap_or_default())
+    }
+}
+
+/// Returns whether the content and the fallback of the suspense are hidden.
+fn hidden(cx: &Context) -> (bool, bool) {
+    cx.with_last_render(|node| {
+        let Some(Node::Element(suspense)) = node else {
+            unreachable!()
+        };
+        let is_hidden = |node: &Node| match node {
+            Node::Element(element) => element.attrs().contains_key("hidden"),
+            _ => unreachable!(),
+        };
+        let children = RenderableElement::children(suspense.as_ref());
+        (is_hidden(&children[0]), is_hidden(&children[1]))
+    })
+}
+
+#[tokio::test]
+async fn suspense_shows_fallback_until_resources_settle() {
+    let (sender, receiver) = oneshot::channel();
+    let receiver = Mutex::new(Some(receiver));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context_registry = Arc::new(ContextRegistry::default());
+    let context = Context::new(
+        Arc::new(Suspense::new(
+            move || DataView {
+                receiver: Mutex::new(receiver.lock().unwrap().take()),
+            },
+            || Node::new("p").child("loading").into(),
+        )),
+        Arc::clone(&context_registry),
+        Arc::clone(&rendering_queue),
+    );
+
+    context.force_render();
+    rendering_queue.flush_effects();
+    assert_eq!(hidden(&context), (true, false));
+
+    sender.send(42).unwrap();
+    // the child view settles first, then the suspense re-renders
+    for _ in 0..2 {
+        tokio::task::yield_now().await;
+        for view_id in rendering_queue.take_views() {
+            context_registry.get(&view_id).unwrap().force_render();
+        }
+        rendering_queue.flush_effects();
+    }
+
+    assert_eq!(hidden(&context), (false, true));
+}
@@ -0,0 +1,213 @@
+mod common;
+
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use blastview::{
+    context::{Context, ViewContext},
+    node::Node,
+    rendering::RenderingQueue,
+    template::{Bindings, Template, TemplateError, Templates},
+    view::View,
+};
+
+use common::describe;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("blastview-templates-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn slots_and_handlers_are_filled_from_bindings() {
+    let template = Template::parse(
+        "counter.html",
+        r#"
+        <div class="counter {{ theme }}">
+          <p>Count: {{ count }}!</p>
+          <button on:click="increment" disabled="{{ at_max }}">+1</button>
+          <input value="{{ missing }}" readonly>
+        </div>
+        "#,
+    )
+    .unwrap();
+
+    let node = template.render(
+        &Bindings::new()
+            .slot("theme", "dark")
+            .slot("count", 3)
+            .flag("at_max", false)
+            .on("increment", || {}),
+    );
+    assert_eq!(
+        describe(&node),
+        r#"<div class="counter dark"><p>Count: 3!</p><button on:click>+1</button><input readonly="" value=""></input></div>"#
+    );
+}
+
+#[test]
+fn node_slots_and_several_roots_make_fragments() {
+    let template = Template::parse("list.html", "<h1>{{ title }}</h1>{{ list }}").unwrap();
+    let node = template.render(
+        &Bindings::new()
+            .slot("title", "<Items>")
+            .node("list", Node::new("ul").child(Node::new("li").child("one"))),
+    );
+    assert!(matches!(node, Node::Fragment(_)));
+    assert_eq!(describe(&node), "<h1><Items></h1><ul><li>one</li></ul>");
+}
+
+#[test]
+fn parse_errors_report_line_and_column() {
+    let error = Template::parse("broken.html", "<div>\n  <p>text</span>\n</div>").unwrap_err();
+    assert_eq!(
+        error,
+        TemplateError::Syntax {
+            template: "broken.html".to_string(),
+            line: 2,
+            column: 10,
+            message: "expected `</p>`, found `</span>`".to_string(),
+        }
+    );
+
+    let error = Template::parse("unclosed.html", "<main>\n<section>{{ x }}").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "template `unclosed.html` at 2:1: unclosed `<section>`"
+    );
+
+    let error = Template::parse("slot.html", "<p>{{ oops </p>").unwrap_err();
+    assert!(error.to_string().ends_with("unterminated `{{`"));
+
+    let error = Template::parse("event.html", "<button on:click>").unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .ends_with("`on:click` needs the name of a handler")
+    );
+}
+
+#[test]
+fn slots_are_rejected_inside_scripts_and_styles() {
+    let error = Template::parse(
+        "script.html",
+        "<p>{{ name }}</p>\n<script>var name = \"{{ name }}\";</script>",
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        TemplateError::Syntax {
+            template: "script.html".to_string(),
+            line: 2,
+            column: 21,
+            message: "slots are not allowed inside `<script>`".to_string(),
+        }
+    );
+
+    let error =
+        Template::parse("style.html", "<style>p { color: {{ color }} }</style>").unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .ends_with("slots are not allowed inside `<style>`")
+    );
+
+    // attribute values and escapable text remain fine
+    assert!(
+        Template::parse(
+            "ok.html",
+            r#"<script src="{{ src }}"></script><title>{{ t }}</title>"#
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn templates_are_reloaded_when_changed_on_disk() {
+    let dir = temp_dir();
+    let path = dir.join("page.html");
+    fs::write(&path, "<p>{{ a }}</p>").unwrap();
+
+    let templates = Templates::new(&dir);
+    let first = templates.get("page.html").unwrap();
+    assert!(templates.reload().is_empty());
+
+    // an edit keeping the length and modification time of the file is seen all the same
+    let modified = fs::metadata(&path).unwrap().modified().unwrap();
+    fs::write(&path, "<p>{{ b }}</p>").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+    assert_eq!(templates.reload(), ["page.html"]);
+    let second = templates.get("page.html").unwrap();
+    assert_ne!(first, second);
+
+    assert!(matches!(
+        templates.get("missing.html"),
+        Err(TemplateError::Io { .. })
+    ));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn views_re_render_when_their_template_is_reloaded() {
+    struct Page {
+        templates: Templates,
+        seen: Arc<Mutex<Vec<String>>>,
+    }
+    impl View for Page {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            let template = self.templates.use_template(cx, "page.html").unwrap();
+            let node = template.render(&Bindings::new().slot("name", "world"));
+            self.seen.lock().unwrap().push(describe(&node));
+            node
+        }
+    }
+
+    let dir = temp_dir();
+    let path = dir.join("page.html");
+    fs::write(&path, "<p>Hello, {{ name }}</p>").unwrap();
+
+    let templates = Templates::new(&dir);
+    let seen = Arc::new(Mutex::new(vec![]));
+    let rendering_queue = Arc::new(RenderingQueue::default());
+    let context = Context::new(
+        Arc::new(Page {
+            templates: templates.clone(),
+            seen: Arc::clone(&seen),
+        }),
+        Default::default(),
+        Arc::clone(&rendering_queue),
+    );
+    context.force_render();
+    rendering_queue.flush_effects();
+
+    // let the subscription start before reloading
+    tokio::task::yield_now().await;
+    fs::write(&path, "<h1>Bye, {{ name }}</h1>").unwrap();
+    templates.reload();
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+
+    assert!(
+        rendering_queue
+            .render_queue
+            .lock()
+            .unwrap()
+            .contains(&context.id)
+    );
+    context.force_render();
+    assert_eq!(
+        *seen.lock().unwrap(),
+        ["<p>Hello, world</p>", "<h1>Bye, world</h1>"]
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,145 @@
+use std::{hint::black_box, sync::Arc};
+
+use blastview::{
+    context::{Context, ViewContext},
+    node::{ElementNode, Node, RenderableElement, RenderableFragment, RenderableText},
+    view::View,
+};
+use criterion::{Criterion, criterion_group, criterion_main};
+use web::Renderer;
+
+struct TreeView {
+    tree: Node,
+}
+
+impl View for TreeView {
+    fn render(&self, _: &impl ViewContext) -> impl Into<Node> {
+        self.tree.clone()
+    }
+}
+
+fn deep_tree(depth: usize) -> Node {
+    (0..depth).fold(Node::text("leaf").into(), |child, depth| {
+        Node::new("div")
+            .attr("class", "nested")
+            .attr("data-depth", &depth.to_string())
+            .child(child)
+            .into()
+    })
+}
+
+fn wide_tree(rows: usize) -> Node {
+    (0..rows)
+        .fold(Node::new("table"), |table, row| {
+            table.child(
+                Node::new("tr")
+                    .child(Node::new("td").child(row))
+                    .child(Node::new("td").child(format!("row <{row}>")))
+                    .child(
+                        Node::new("td").child(Node::new("button").child("x").on("click", || {})),
+                    ),
+            )
+        })
+        .into()
+}
+
+/// The renderer as it was before writing into sinks, building one `String` per node and
+/// copying it into its parent's, kept as the baseline of the comparison.
+mod baseline {
+    use super::*;
+
+    pub fn render_node_to_string(node: &Node) -> String {
+        match node {
+            Node::Element(node) => render_element_node_to_string(node),
+            Node::Text(text) => {
+                html_escape::encode_text(RenderableText::text(text.as_ref())).to_string()
+            }
+            Node::Fragment(fragment) => RenderableFragment::children(fragment.as_ref())
+                .iter()
+                .map(render_node_to_string)
+                .collect(),
+            _ => unreachable!("the benchmarked trees hold elements and text only"),
+        }
+    }
+
+    fn render_element_node_to_string(node: &ElementNode) -> String {
+        let mut buffer = String::new();
+
+        buffer.push('<');
+        buffer.push_str(RenderableElement::tag(node));
+        for (attr, value) in RenderableElement::attrs(node).iter() {
+            buffer.push(' ');
+            buffer.push_str(attr);
+            buffer.push_str("=\"");
+            buffer.push_str(&html_escape::encode_quoted_attribute(value));
+            buffer.push('"');
+        }
+        buffer.push_str(" data-id=\"");
+        buffer.push_str(&RenderableElement::id(node).to_string());
+        buffer.push('"');
+        if !RenderableElement::events(node).is_empty() {
+            buffer.push_str(" data-events=\"");
+            buffer.push_str(
+                &RenderableElement::events(node)
+                    .keys()
+                    .cloned()
+                    .reduce(|acc, s| format!("{acc},{s}"))
+                    .unwrap(),
+            );
+            buffer.push('"');
+        }
+        buffer.push('>');
+
+        for child in RenderableElement::children(node).iter() {
+            buffer.push_str(&render_node_to_string(child));
+        }
+
+        buffer.push_str("</");
+        buffer.push_str(RenderableElement::tag(node));
+        buffer.push('>');
+
+        buffer
+    }
+}
+
+fn renderer(tree: Node) -> (Arc<Context>, Renderer) {
+    let context = Context::new(
+        Arc::new(TreeView { tree }),
+        Default::default(),
+        Default::default(),
+    );
+    let renderer = Renderer::new(Arc::clone(&context));
+    // the first render builds the tree, later ones only serialize it
+    renderer.render_to_string();
+    (context, renderer)
+}
+
+fn render(c: &mut Criterion) {
+    for (name, tree) in [("deep", deep_tree(1_000)), ("wide", wide_tree(10_000))] {
+        let (context, renderer) = renderer(tree);
+
+        c.bench_function(&format!("{name}/per_node_strings"), |b| {
+            b.iter(|| {
+                black_box(
+                    context.with_last_render(|node| baseline::render_node_to_string(node.unwrap())),
+                )
+            })
+        });
+
+        c.bench_function(&format!("{name}/render_to_string"), |b| {
+            b.iter(|| black_box(renderer.render_to_string()))
+        });
+
+        let mut html = String::new();
+        c.bench_function(&format!("{name}/render_to_writer"), |b| {
+            b.iter(|| {
+                html.clear();
+                renderer.render_to_writer(&mut html).unwrap();
+                black_box(html.len())
+            })
+        });
+    }
+}
+
+criterion_group!(benches, render);
+criterion_main!(benches);
@@ -1,19 +1,42 @@
+pub mod policy;
 pub mod session;
 
-use std::sync::Arc;
+use std::{
+    borrow::Cow,
+    fmt::{self, Write},
+    io,
+    sync::Arc,
+};
 
 use blastview::{
     context::Context,
-    node::{ElementNode, Node, RenderableElement, RenderableText},
+    node::{
+        ElementKind, ElementNode, Node, RenderableElement, RenderableFragment, RenderableRawHtml,
+        RenderableSignal, RenderableText,
+    },
 };
 
+use crate::policy::AttributePolicy;
+
 pub struct Renderer {
     root_context: Arc<Context>,
+    policy: AttributePolicy,
 }
 
 impl Renderer {
     pub fn new(root_context: Arc<Context>) -> Self {
-        Self { root_context }
+        Self::with_policy(root_context, AttributePolicy::default())
+    }
+
+    pub fn with_policy(root_context: Arc<Context>, policy: AttributePolicy) -> Self {
+        Self {
+            root_context,
+            policy,
+        }
+    }
+
+    pub fn policy(&self) -> &AttributePolicy {
+        &self.policy
     }
 }
 
@@ -22,74 +45,201 @@ impl Renderer {
         self.render_view_to_string(&self.root_context)
     }
 
-    fn render_view_to_string(&self, cx: &Context) -> String {
-        cx.render();
+    /// Renders the page into `w`, without allocating intermediate strings.
+    pub fn render_to_writer(&self, w: &mut impl Write) -> fmt::Result {
+        self.render_view_to_writer(&self.root_context, w)
+    }
 
-        let node = cx.with_last_render(|node| cx.view_node().child(node.unwrap().clone()).into());
+    /// Renders the page into an I/O sink, such as a file or a socket.
+    pub fn render_to_io(&self, w: &mut impl io::Write) -> io::Result<()> {
+        let mut adapter = IoAdapter {
+            inner: w,
+            error: None,
+        };
+        self.render_to_writer(&mut adapter).map_err(|_| {
+            adapter
+                .error
+                .unwrap_or_else(|| io::Error::other("formatting failed"))
+        })
+    }
 
-        self.render_node_to_string(&node, &cx)
+    pub fn render_view_to_string(&self, cx: &Context) -> String {
+        let mut html = String::new();
+        self.render_view_to_writer(cx, &mut html)
+            .expect("writing to a String cannot fail");
+        html
+    }
+
+    pub fn render_view_to_writer(&self, cx: &Context, w: &mut impl Write) -> fmt::Result {
+        cx.render();
+
+        let view_node = cx.view_node();
+        self.write_open_tag(&view_node, w)?;
+        cx.with_last_render(|node| self.render_node_to_writer(node.unwrap(), cx, w))?;
+        self.write_close_tag(&view_node, w)
     }
 
     pub fn render_node_to_string(&self, node: &Node, cx: &Context) -> String {
+        let mut html = String::new();
+        self.render_node_to_writer(node, cx, &mut html)
+            .expect("writing to a String cannot fail");
+        html
+    }
+
+    pub fn render_node_to_writer(
+        &self,
+        node: &Node,
+        cx: &Context,
+        w: &mut impl Write,
+    ) -> fmt::Result {
         match node {
-            Node::Element(node) => self.render_element_node_to_string(&node, cx),
-            Node::Text(text) => {
-                html_escape::encode_text(RenderableText::text(text.as_ref())).to_string()
+            Node::Element(node) => {
+                let children = RenderableElement::children(node.as_ref());
+                self.write_open_tag(node, w)?;
+                match ElementKind::of(RenderableElement::tag(node.as_ref())) {
+                    ElementKind::Void => return Ok(()),
+                    ElementKind::Normal => {
+                        for child in children {
+                            self.render_node_to_writer(child, cx, w)?;
+                        }
+                    }
+                    // only text can appear in these elements, which cannot host live signals
+                    ElementKind::RawText => {
+                        for text in children.iter().filter_map(text_content) {
+                            write_raw_text(w, RenderableElement::tag(node.as_ref()), &text)?;
+                        }
+                    }
+                    ElementKind::EscapableRawText => {
+                        for text in children.iter().filter_map(text_content) {
+                            w.write_str(&html_escape::encode_text(&text))?;
+                        }
+                    }
+                }
+                self.write_close_tag(node, w)
+            }
+            Node::Text(text) => w.write_str(&html_escape::encode_text(RenderableText::text(
+                text.as_ref(),
+            ))),
+            Node::ViewRef(view) => {
+                self.render_view_to_writer(&cx.get_child(view.order).unwrap(), w)
+            }
+            Node::Signal(signal) => {
+                write!(
+                    w,
+                    r#"<bv-signal data-id="{}">"#,
+                    RenderableSignal::id(signal.as_ref())
+                )?;
+                w.write_str(&html_escape::encode_text(&RenderableSignal::text(
+                    signal.as_ref(),
+                )))?;
+                w.write_str("</bv-signal>")
+            }
+            Node::Fragment(fragment) => {
+                for child in RenderableFragment::children(fragment.as_ref()) {
+                    self.render_node_to_writer(child, cx, w)?;
+                }
+                Ok(())
+            }
+            // wrapped, so that it remains a single node that patches can replace
+            Node::RawHtml(raw) => {
+                write!(
+                    w,
+                    r#"<bv-raw data-id="{}">"#,
+                    RenderableRawHtml::id(raw.as_ref())
+                )?;
+                w.write_str(RenderableRawHtml::html(raw.as_ref()))?;
+                w.write_str("</bv-raw>")
             }
-            Node::ViewRef(view) => self.render_view_to_string(&cx.get_child(view.order).unwrap()),
         }
     }
 
-    fn render_element_node_to_string(&self, node: &ElementNode, cx: &Context) -> String {
-        let mut buffer = String::new();
-
-        buffer.push('<');
-        buffer.push_str(RenderableElement::tag(node));
+    fn write_open_tag(&self, node: &ElementNode, w: &mut impl Write) -> fmt::Result {
+        w.write_char('<')?;
+        w.write_str(RenderableElement::tag(node))?;
 
         for (attr, value) in RenderableElement::attrs(node).iter() {
-            if attr.starts_with("on") {
-                continue;
+            if self.policy.allows(attr, value) {
+                write_attr(w, attr, value)?;
             }
-            buffer.push(' ');
-            buffer.push_str(attr);
-            buffer.push('=');
-            buffer.push('"');
-            buffer.push_str(&html_escape::encode_quoted_attribute(value).to_string());
-            buffer.push('"');
         }
 
-        buffer.push(' ');
-        buffer.push_str("data-id");
-        buffer.push('=');
-        buffer.push('"');
-        buffer.push_str(&RenderableElement::id(node).to_string());
-        buffer.push('"');
-        if !RenderableElement::events(node).is_empty() {
-            buffer.push(' ');
-            buffer.push_str("data-events");
-            buffer.push('=');
-            buffer.push('"');
-            buffer.push_str(
-                &RenderableElement::events(node)
-                    .keys()
-                    .cloned()
-                    .reduce(|acc, s| format!("{acc},{s}"))
-                    .unwrap(),
-            );
-            buffer.push('"');
+        for (attr, signal) in RenderableElement::bindings(node).iter() {
+            let value = signal.render();
+            if self.policy.allows(attr, &value) {
+                write_attr(w, attr, &value)?;
+            }
         }
 
-        buffer.push('>');
+        write!(w, r#" data-id="{}""#, RenderableElement::id(node))?;
 
-        for child in RenderableElement::children(node).iter() {
-            buffer.push_str(&self.render_node_to_string(child, cx));
+        let mut events = RenderableElement::events(node).keys();
+        if let Some(first) = events.next() {
+            w.write_str(r#" data-events=""#)?;
+            w.write_str(first)?;
+            for event in events {
+                w.write_char(',')?;
+                w.write_str(event)?;
+            }
+            w.write_char('"')?;
         }
 
-        buffer.push('<');
-        buffer.push('/');
-        buffer.push_str(RenderableElement::tag(node));
-        buffer.push('>');
+        w.write_char('>')
+    }
+
+    fn write_close_tag(&self, node: &ElementNode, w: &mut impl Write) -> fmt::Result {
+        w.write_str("</")?;
+        w.write_str(RenderableElement::tag(node))?;
+        w.write_char('>')
+    }
+}
+
+/// Writes an attribute, leaving out empty values so that boolean attributes are written bare.
+fn write_attr(w: &mut impl Write, attr: &str, value: &str) -> fmt::Result {
+    w.write_char(' ')?;
+    w.write_str(attr)?;
+    if value.is_empty() {
+        return Ok(());
+    }
+    w.write_str("=\"")?;
+    w.write_str(&html_escape::encode_quoted_attribute(value))?;
+    w.write_char('"')
+}
+
+fn text_content(node: &Node) -> Option<Cow<'_, str>> {
+    match node {
+        Node::Text(text) => Some(Cow::Borrowed(RenderableText::text(text.as_ref()))),
+        Node::Signal(signal) => Some(Cow::Owned(RenderableSignal::text(signal.as_ref()))),
+        _ => None,
+    }
+}
+
+/// Writes the text of a `<script>` or `<style>` element as is,
+/// except for sequences that would close the element early.
+fn write_raw_text(w: &mut impl Write, tag: &str, text: &str) -> fmt::Result {
+    let mut rest = text;
+    while let Some(at) = rest.find("</") {
+        let after = &rest[at + 2..];
+        let closes = after
+            .get(..tag.len())
+            .is_some_and(|name| name.eq_ignore_ascii_case(tag));
+        w.write_str(&rest[..at])?;
+        w.write_str(if closes { "<\\/" } else { "</" })?;
+        rest = after;
+    }
+    w.write_str(rest)
+}
+
+/// Forwards formatted output to an I/O sink, keeping the I/O error that `fmt::Error` cannot carry.
+struct IoAdapter<'a, W: io::Write> {
+    inner: &'a mut W,
+    error: Option<io::Error>,
+}
 
-        buffer
+impl<W: io::Write> Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
     }
 }
@@ -0,0 +1,69 @@
+use blastview::html;
+
+/// Attributes written by the renderer itself, which views may not override.
+const RESERVED_ATTRIBUTES: &[&str] = &["data-id", "data-events"];
+
+/// Decides which attributes reach the client, both in rendered HTML and in patches.
+///
+/// Attribute names must be valid HTML names, and may not be event handlers such as `onclick`.
+/// URL-bearing attributes such as `href` are dropped unless their URL is relative
+/// or uses an allowed scheme.
+#[derive(Debug, Clone)]
+pub struct AttributePolicy {
+    url_schemes: Vec<String>,
+}
+
+impl Default for AttributePolicy {
+    fn default() -> Self {
+        Self::with_url_schemes(["http", "https", "mailto", "tel"])
+    }
+}
+
+impl AttributePolicy {
+    pub fn with_url_schemes<S: AsRef<str>>(schemes: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            url_schemes: schemes
+                .into_iter()
+                .map(|scheme| scheme.as_ref().to_ascii_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Whether an attribute may be written with this name, whatever its value.
+    pub fn allows_name(&self, name: &str) -> bool {
+        is_valid_name(name)
+            && !name
+                .get(..2)
+                .is_some_and(|on| on.eq_ignore_ascii_case("on"))
+            && !RESERVED_ATTRIBUTES
+                .iter()
+                .any(|reserved| reserved.eq_ignore_ascii_case(name))
+    }
+
+    /// Whether an attribute may be written with this name and value.
+    pub fn allows(&self, name: &str, value: &str) -> bool {
+        if !self.allows_name(name) {
+            return false;
+        }
+
+        let is_url = html::is_url_attribute(name);
+        match html::url_scheme(value) {
+            Some(scheme) if is_url => self
+                .url_schemes
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(&scheme)),
+            _ => true,
+        }
+    }
+}
+
+// https://html.spec.whatwg.org/#attributes-2
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|c| {
+            !c.is_control()
+                && !c.is_whitespace()
+                && !matches!(c, '"' | '\'' | '>' | '/' | '=' | '<' | '`')
+                && !('\u{FDD0}'..='\u{FDEF}').contains(&c)
+        })
+}
@@ -1,19 +1,23 @@
 pub mod patch;
+mod stream;
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use blastview::{
-    context::{Context, NodePatch, context_registry::ContextRegistry, events::Event},
+    context::{Boundary, Context, NodePatch, context_registry::ContextRegistry, events::Event},
+    node::Node,
     rendering::RenderingQueue,
-    view::View,
+    view::{RenderableView, ViewError},
 };
-use tokio::sync::Notify;
+use tokio::{sync::Notify, time::Instant};
 use tokio_util::sync::CancellationToken;
 
-use crate::{Renderer, session::patch::Patch};
+use crate::{Renderer, policy::AttributePolicy, session::patch::Patch};
+pub use stream::RenderChunk;
 
 pub struct LiveSession {
     context: Arc<Context>,
+    boundary: Boundary,
     renderer: Renderer,
     rendering_queue: Arc<RenderingQueue>,
     context_registry: Arc<ContextRegistry>,
@@ -25,23 +29,35 @@ pub struct LiveSession {
 impl LiveSession {
     pub fn new<V, F>(factory: F) -> (Self, flume::Receiver<Patch>)
     where
-        V: View + Send + Sync + 'static,
+        V: RenderableView + Send + Sync + 'static,
+        F: Fn() -> V + Send + Sync,
+    {
+        Self::with_policy(factory, AttributePolicy::default())
+    }
+
+    /// Creates a session whose rendered HTML and patches only carry attributes allowed by `policy`.
+    pub fn with_policy<V, F>(factory: F, policy: AttributePolicy) -> (Self, flume::Receiver<Patch>)
+    where
+        V: RenderableView + Send + Sync + 'static,
         F: Fn() -> V + Send + Sync,
     {
         let rendering_queue = Default::default();
         let context_registry = Default::default();
-        let context = Context::new(
+        let boundary = Boundary::root();
+        let context = Context::with_boundary(
             Arc::new(factory()),
             Arc::clone(&context_registry),
             Arc::clone(&rendering_queue),
+            Some(boundary.clone()),
         );
-        let renderer = Renderer::new(Arc::clone(&context));
+        let renderer = Renderer::with_policy(Arc::clone(&context), policy);
 
         let (patch_tx, patch_rx) = flume::unbounded();
 
         (
             Self {
                 context,
+                boundary,
                 renderer,
                 rendering_queue,
                 context_registry,
@@ -62,95 +78,167 @@ impl LiveSession {
         self.context.dispatch_event(&event);
     }
 
+    /// The first error not caught by any error boundary in the page.
+    pub fn error(&self) -> Option<ViewError> {
+        self.boundary.error()
+    }
+
     pub fn dynamic_render(&self) -> String {
+        let html = self.renderer.render_to_string();
+        self.rendering_queue.flush_effects();
+        html
+    }
+
+    /// Renders the page once every resource in it has settled, or once `timeout` elapses.
+    ///
+    /// Views are re-rendered as their resources settle, without sending patches,
+    /// so the session resumes from the returned state when the client connects.
+    pub async fn settled_render(&self, timeout: Duration) -> String {
+        self.renderer.render_to_string();
+        self.rendering_queue.flush_effects();
+
+        let deadline = Instant::now() + timeout;
+        while self.rendering_queue.has_pending_resources() {
+            let enqueued = self.rendering_queue.enqueued();
+            if tokio::time::timeout_at(deadline, enqueued).await.is_err() {
+                // the rest is patched in once the client connects
+                break;
+            }
+            self.re_render_silently();
+        }
+        // settling may have enqueued suspense boundaries
+        self.re_render_silently();
+
+        // the client receives the current state in full
+        self.rendering_queue.take_signal_patches();
+        self.rendering_queue.take_patches();
+
         self.renderer.render_to_string()
     }
 
+    /// Re-renders the enqueued views without sending patches, returning them.
+    fn re_render_silently(&self) -> Vec<Arc<Context>> {
+        let mut views = vec![];
+        for view_id in self.rendering_queue.take_views() {
+            if let Some(cx) = self.context_registry.get(&view_id) {
+                cx.force_render();
+                views.push(cx);
+            }
+        }
+        self.rendering_queue.flush_effects();
+        views
+    }
+
     async fn process_re_render_queue(&self) {
-        if self.rendering_queue.render_queue.lock().unwrap().is_empty()
-            && self
-                .rendering_queue
-                .deferred_queue
-                .lock()
-                .unwrap()
-                .is_empty()
-        {
+        if self.rendering_queue.is_empty() {
             return;
         }
 
-        let process_view = |view_id: uuid::Uuid| {
-            if self.patch_sender.is_disconnected() {
-                return;
-            }
-            let cx = self.context_registry.get(&view_id).unwrap();
-            let patches = cx.force_render();
-            let patches = patches
+        if self.patch_sender.is_disconnected() {
+            return;
+        }
+
+        // patches from every view processed in this pass are sent together,
+        // so updates made in a single `batch` reach the client at once
+        let mut patches = vec![];
+        for view_id in self.rendering_queue.take_views() {
+            // the view may have been dropped since it was enqueued
+            let Some(cx) = self.context_registry.get(&view_id) else {
+                continue;
+            };
+            patches.extend(
+                cx.force_render()
+                    .into_iter()
+                    .filter_map(|patch| self.to_patch(patch, &cx)),
+            );
+        }
+
+        patches.extend(
+            self.rendering_queue
+                .take_signal_patches()
                 .into_iter()
-                .map(|patch| match patch {
-                    NodePatch::ReplaceViewChildren { view_id, children } => Patch::ReplaceInner {
-                        selector: format!(r#"bv-view[data-view="{view_id}"]"#),
-                        html: children
-                            .into_iter()
-                            .map(|child| self.renderer.render_node_to_string(&child, &cx))
-                            .collect(),
-                    },
-                    NodePatch::ReplaceChildren { node_id, children } => Patch::ReplaceInner {
-                        selector: format!(r#"[data-id="{node_id}"]"#),
-                        html: children
-                            .into_iter()
-                            .map(|child| self.renderer.render_node_to_string(&child, &cx))
-                            .collect(),
-                    },
-                    NodePatch::ReplaceChild {
-                        node_id,
-                        child_idx,
-                        node,
-                    } => Patch::ReplaceChild {
-                        selector: format!(r#"[data-id="{node_id}"]"#),
-                        index: child_idx,
-                        html: self.renderer.render_node_to_string(&node, &cx),
-                    },
-                    NodePatch::Replace { node_id, node } => Patch::ReplaceOuter {
-                        selector: format!(r#"[data-id="{node_id}"]"#),
-                        html: self.renderer.render_node_to_string(&node, &cx),
-                    },
-                    NodePatch::SetAttr {
-                        node_id,
-                        attr,
-                        value,
-                    } => Patch::SetAttribute {
-                        selector: format!(r#"[data-id="{node_id}"]"#),
-                        name: attr,
-                        value,
-                    },
-                    NodePatch::RemoveAttr { node_id, attr } => Patch::RemoveAttribute {
-                        selector: format!(r#"[data-id="{node_id}"]"#),
-                        name: attr,
-                    },
-                    NodePatch::AttachEvent { node_id, event } => Patch::AttachEvent {
-                        selector: format!(r#"[data-id="{node_id}"]"#),
-                        event,
-                    },
-                    NodePatch::DetachEvent { node_id, event } => Patch::DetachEvent {
-                        selector: format!(r#"[data-id="{node_id}"]"#),
-                        event,
-                    },
-                })
-                .collect();
-
-            if self.patch_sender.is_disconnected() {
-                return;
-            }
-            self.patch_sender.send(Patch::Batch { patches }).unwrap();
-        };
+                .chain(self.rendering_queue.take_patches())
+                .filter_map(|patch| self.to_patch(patch, &self.context)),
+        );
 
-        for view_id in self.rendering_queue.render_queue.lock().unwrap().drain() {
-            process_view(view_id);
+        if !patches.is_empty() && !self.patch_sender.is_disconnected() {
+            self.patch_sender.send(Patch::Batch { patches }).unwrap();
         }
 
-        for view_id in self.rendering_queue.deferred_queue.lock().unwrap().drain() {
-            process_view(view_id);
+        self.rendering_queue.flush_effects();
+    }
+
+    /// Renders sibling nodes into a single buffer.
+    fn render_children(&self, children: &[Node], cx: &Context) -> String {
+        let mut html = String::new();
+        for child in children {
+            self.renderer
+                .render_node_to_writer(child, cx, &mut html)
+                .expect("writing to a String cannot fail");
         }
+        html
+    }
+
+    /// Converts a patch for the client, or drops it if it sets an attribute the policy forbids.
+    fn to_patch(&self, patch: NodePatch, cx: &Context) -> Option<Patch> {
+        let policy = self.renderer.policy();
+        let patch = match patch {
+            NodePatch::ReplaceViewChildren { view_id, children } => Patch::ReplaceInner {
+                selector: format!(r#"bv-view[data-view="{view_id}"]"#),
+                html: self.render_children(&children, cx),
+            },
+            NodePatch::ReplaceChildren { node_id, children } => Patch::ReplaceInner {
+                selector: format!(r#"[data-id="{node_id}"]"#),
+                html: self.render_children(&children, cx),
+            },
+            NodePatch::ReplaceChild {
+                node_id,
+                child_idx,
+                node,
+            } => Patch::ReplaceChild {
+                selector: format!(r#"[data-id="{node_id}"]"#),
+                index: child_idx,
+                html: self.renderer.render_node_to_string(&node, cx),
+            },
+            NodePatch::Replace { node_id, node } => Patch::ReplaceOuter {
+                selector: format!(r#"[data-id="{node_id}"]"#),
+                html: self.renderer.render_node_to_string(&node, cx),
+            },
+            NodePatch::SetAttr { attr, .. } | NodePatch::RemoveAttr { attr, .. }
+                if !policy.allows_name(&attr) =>
+            {
+                return None;
+            }
+            NodePatch::SetAttr {
+                node_id,
+                attr,
+                value,
+            } if policy.allows(&attr, &value) => Patch::SetAttribute {
+                selector: format!(r#"[data-id="{node_id}"]"#),
+                name: attr,
+                value,
+            },
+            // the previous value may have been allowed
+            NodePatch::SetAttr { node_id, attr, .. } | NodePatch::RemoveAttr { node_id, attr } => {
+                Patch::RemoveAttribute {
+                    selector: format!(r#"[data-id="{node_id}"]"#),
+                    name: attr,
+                }
+            }
+            NodePatch::SetText { node_id, text } => Patch::SetText {
+                selector: format!(r#"[data-id="{node_id}"]"#),
+                text,
+            },
+            NodePatch::AttachEvent { node_id, event } => Patch::AttachEvent {
+                selector: format!(r#"[data-id="{node_id}"]"#),
+                event,
+            },
+            NodePatch::DetachEvent { node_id, event } => Patch::DetachEvent {
+                selector: format!(r#"[data-id="{node_id}"]"#),
+                event,
+            },
+        };
+        Some(patch)
     }
 
     pub fn stop_re_render_task(&self) {
@@ -20,6 +20,10 @@ pub enum Patch {
         selector: String,
         name: String,
     },
+    SetText {
+        selector: String,
+        text: String,
+    },
     AppendChild {
         selector: String,
         html: String,
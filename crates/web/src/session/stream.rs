@@ -0,0 +1,71 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::{Stream, StreamExt, stream};
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::session::LiveSession;
+
+/// A piece of a streamed page.
+#[derive(Debug)]
+pub enum RenderChunk {
+    /// The page as first rendered, with its resources possibly still loading.
+    Page(String),
+    /// A view re-rendered once its resources settled, replacing the one sent before.
+    View { view_id: Uuid, html: String },
+}
+
+enum Stage {
+    Page,
+    Views { deadline: Instant },
+    Done,
+}
+
+impl LiveSession {
+    /// Renders the page right away, then re-renders views as their resources settle.
+    ///
+    /// The stream ends once every resource has settled, or once `timeout` elapses.
+    /// As with [`LiveSession::settled_render`], no patches are sent for these re-renders.
+    pub fn render_stream(self: Arc<Self>, timeout: Duration) -> impl Stream<Item = RenderChunk> {
+        stream::unfold((self, Stage::Page), move |(session, stage)| async move {
+            match stage {
+                Stage::Page => {
+                    let html = session.dynamic_render();
+                    let deadline = Instant::now() + timeout;
+                    Some((
+                        vec![RenderChunk::Page(html)],
+                        (session, Stage::Views { deadline }),
+                    ))
+                }
+                Stage::Views { deadline } => {
+                    let is_settling = session.rendering_queue.has_pending_resources()
+                        && tokio::time::timeout_at(deadline, session.rendering_queue.enqueued())
+                            .await
+                            .is_ok();
+
+                    let chunks = session.view_chunks();
+                    if is_settling {
+                        return Some((chunks, (session, Stage::Views { deadline })));
+                    }
+
+                    // the client receives the current state in full
+                    session.rendering_queue.take_signal_patches();
+                    session.rendering_queue.take_patches();
+                    Some((chunks, (session, Stage::Done)))
+                }
+                Stage::Done => None,
+            }
+        })
+        .flat_map(stream::iter)
+    }
+
+    fn view_chunks(&self) -> Vec<RenderChunk> {
+        self.re_render_silently()
+            .into_iter()
+            .map(|cx| RenderChunk::View {
+                view_id: cx.id,
+                html: self.renderer.render_view_to_string(&cx),
+            })
+            .collect()
+    }
+}
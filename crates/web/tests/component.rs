@@ -1,12 +1,32 @@
-use std::sync::Arc;
+use std::{io, sync::Arc};
 
 use blastview::{
     context::{Context, ViewContext},
-    node::Node,
+    html::{Sanitizer, Trusted},
+    node::{Node, RenderableElement},
     view::{RenderableView, View},
 };
 use web::Renderer;
 
+fn render(node: impl Into<Node>) -> String {
+    struct EmptyView;
+    impl View for EmptyView {
+        fn render(&self, _: &impl ViewContext) -> impl Into<Node> {
+            ""
+        }
+    }
+
+    let context = Context::new(Arc::new(EmptyView), Default::default(), Default::default());
+    Renderer::new(Arc::clone(&context)).render_node_to_string(&node.into(), &context)
+}
+
+fn id(node: &Node) -> String {
+    match node {
+        Node::Element(element) => element.id().to_string(),
+        _ => unreachable!(),
+    }
+}
+
 #[test]
 fn view_rendering_works() {
     struct MyView;
@@ -21,7 +41,136 @@ fn view_rendering_works() {
     let context = Context::new(Arc::new(MyView), Default::default(), Default::default());
 
     let renderer = Renderer::new(Arc::clone(&context));
-    let html =
-        renderer.render_node_to_string(&RenderableView::render(&MyView, &context).into(), &context);
-    assert_eq!(html, r#"<div class="container">Hello world!</div>"#);
+    let node = RenderableView::render(&MyView, &context).unwrap();
+    let html = renderer.render_node_to_string(&node, &context);
+    assert_eq!(
+        html,
+        format!(
+            r#"<div class="container" data-id="{}">Hello world!</div>"#,
+            id(&node)
+        )
+    );
+}
+
+#[test]
+fn void_elements_have_no_end_tag() {
+    let node: Node = Node::new("input").attr("type", "text").into();
+    assert_eq!(
+        render(node.clone()),
+        format!(r#"<input type="text" data-id="{}">"#, id(&node))
+    );
+}
+
+#[test]
+fn boolean_attributes_are_written_without_value() {
+    let node: Node = Node::new("button")
+        .flag("disabled", true)
+        .flag("hidden", false)
+        .into();
+    assert_eq!(
+        render(node.clone()),
+        format!(r#"<button disabled data-id="{}"></button>"#, id(&node))
+    );
+}
+
+#[test]
+fn attributes_are_written_in_a_stable_order() {
+    let node: Node = Node::new("a")
+        .attr("title", "t")
+        .attr("href", "/")
+        .attr("class", "c")
+        .into();
+    assert_eq!(
+        render(node.clone()),
+        format!(
+            r#"<a class="c" href="/" title="t" data-id="{}"></a>"#,
+            id(&node)
+        )
+    );
+}
+
+#[test]
+fn raw_text_elements_are_not_escaped() {
+    let node: Node = Node::new("script")
+        .child("if (a < b && c) { x = '</SCRIPT>'; }")
+        .into();
+    assert_eq!(
+        render(node.clone()),
+        format!(
+            r#"<script data-id="{}">if (a < b && c) {{ x = '<\/SCRIPT>'; }}</script>"#,
+            id(&node)
+        )
+    );
+
+    let node: Node = Node::new("textarea").child("a < b").into();
+    assert_eq!(
+        render(node.clone()),
+        format!(r#"<textarea data-id="{}">a &lt; b</textarea>"#, id(&node))
+    );
+}
+
+#[test]
+fn raw_html_is_written_unescaped() {
+    let html = render(Trusted::assume_safe("<em>hi</em>"));
+    assert!(html.starts_with("<bv-raw data-id="), "{html}");
+    assert!(html.ends_with(r#""><em>hi</em></bv-raw>"#), "{html}");
+
+    let html = render(Sanitizer::default().sanitize("<em onclick=\"steal()\">hi</em>"));
+    assert!(html.ends_with(r#""><em>hi</em></bv-raw>"#), "{html}");
+}
+
+#[test]
+fn fragments_and_options_render_their_nodes_only() {
+    let note: Option<&str> = None;
+    let html = render(
+        Node::new("p")
+            .children(note)
+            .children(["a", "b"])
+            .child(Node::fragment([Node::fragment(["c"]), "d".into()])),
+    );
+    assert!(html.ends_with(r#"">abcd</p>"#), "{html}");
+}
+
+struct PageView;
+
+impl View for PageView {
+    fn render(&self, _: &impl ViewContext) -> impl Into<Node> {
+        Node::new("main").child(Node::new("p").child("<hello> & goodbye"))
+    }
+}
+
+#[test]
+fn rendering_to_io_writes_the_same_html_as_to_a_string() {
+    let context = Context::new(Arc::new(PageView), Default::default(), Default::default());
+    let renderer = Renderer::new(Arc::clone(&context));
+
+    let mut html = String::new();
+    renderer.render_to_writer(&mut html).unwrap();
+    let mut bytes = vec![];
+    renderer.render_to_io(&mut bytes).unwrap();
+
+    assert_eq!(html, renderer.render_to_string());
+    assert_eq!(String::from_utf8(bytes).unwrap(), html);
+}
+
+#[test]
+fn rendering_to_io_returns_the_error_of_the_writer() {
+    struct FullDisk;
+    impl io::Write for FullDisk {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::StorageFull, "disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let context = Context::new(Arc::new(PageView), Default::default(), Default::default());
+    let error = Renderer::new(context)
+        .render_to_io(&mut FullDisk)
+        .unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::StorageFull);
+    assert_eq!(error.to_string(), "disk full");
 }
@@ -0,0 +1,78 @@
+use std::sync::{Arc, Mutex};
+
+use blastview::{context::ViewContext, node::Node, signal::Signal, view::View};
+use web::{
+    policy::AttributePolicy,
+    session::{LiveSession, patch::Patch},
+};
+
+struct LinkView {
+    handle: Arc<Mutex<Option<Signal<String>>>>,
+}
+
+impl View for LinkView {
+    fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+        let href = cx.use_signal("/home".to_string());
+        *self.handle.lock().unwrap() = Some(href.clone());
+        Node::new("div")
+            .child(
+                Node::new("a")
+                    .attr("onclick", "steal()")
+                    .attr("x\" onmouseover=\"steal()", "")
+                    .attr("data-id", "forged")
+                    .bind("href", href),
+            )
+            .child(Node::new("img").attr("src", " JaVa\tScRiPt:steal()"))
+            .child(Node::new("a").attr("href", "ftp://example.com/file"))
+    }
+}
+
+fn link_view(handle: &Arc<Mutex<Option<Signal<String>>>>) -> impl Fn() -> LinkView + use<> {
+    let handle = Arc::clone(handle);
+    move || LinkView {
+        handle: Arc::clone(&handle),
+    }
+}
+
+#[test]
+fn forbidden_attributes_are_not_rendered() {
+    let (session, _) = LiveSession::new(link_view(&Default::default()));
+    let html = session.dynamic_render();
+
+    assert!(html.contains(r#"href="/home""#), "{html}");
+    assert!(!html.contains("steal"), "{html}");
+    assert!(!html.contains("forged"), "{html}");
+    assert!(!html.contains("ftp:"), "{html}");
+}
+
+#[test]
+fn allowed_url_schemes_are_configurable() {
+    let (session, _) = LiveSession::with_policy(
+        link_view(&Default::default()),
+        AttributePolicy::with_url_schemes(["https", "ftp"]),
+    );
+    let html = session.dynamic_render();
+
+    assert!(html.contains(r#"href="ftp://example.com/file""#), "{html}");
+}
+
+#[tokio::test(start_paused = true)]
+async fn forbidden_attribute_patch_removes_the_attribute() {
+    let handle = Default::default();
+    let (session, patch_rx) = LiveSession::new(link_view(&handle));
+    session.dynamic_render();
+    let session = Arc::new(session);
+    Arc::clone(&session).begin_re_render_task();
+
+    let href = handle.lock().unwrap().clone().unwrap();
+    href.set("javascript:steal()".to_string());
+
+    let Patch::Batch { patches } = patch_rx.recv_async().await.unwrap() else {
+        panic!("expected a batch");
+    };
+    assert!(
+        matches!(&patches[..], [Patch::RemoveAttribute { name, .. }] if name == "href"),
+        "{patches:?}"
+    );
+    session.stop_re_render_task();
+}
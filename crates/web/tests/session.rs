@@ -0,0 +1,102 @@
+use std::{sync::Arc, time::Duration};
+
+use blastview::{
+    context::ViewContext,
+    node::Node,
+    view::{ErrorBoundary, TryView, View},
+};
+use futures::StreamExt;
+use web::session::{LiveSession, RenderChunk};
+
+struct FailingView;
+
+impl TryView for FailingView {
+    type Error = &'static str;
+
+    fn try_render(&self, _: &impl ViewContext) -> Result<impl Into<Node>, Self::Error> {
+        Err::<Node, _>("database unavailable")
+    }
+}
+
+#[test]
+fn uncaught_error_reaches_the_session() {
+    let (session, _) = LiveSession::new(|| FailingView);
+    session.dynamic_render();
+
+    let error = session.error().unwrap();
+    assert_eq!(error.message(), "database unavailable");
+    assert!(error.view().ends_with("FailingView"));
+}
+
+#[test]
+fn error_caught_by_boundary_does_not_reach_the_session() {
+    struct PageView;
+    impl View for PageView {
+        fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+            Node::new("main").child(
+                cx.create_view(|| ErrorBoundary::new(|| FailingView, |_, _| Node::new("p").into())),
+            )
+        }
+    }
+
+    let (session, _) = LiveSession::new(|| PageView);
+    let html = session.dynamic_render();
+
+    assert!(session.error().is_none());
+    assert!(html.contains("<p "), "{html}");
+}
+
+struct SlowView {
+    delay: Duration,
+}
+
+impl View for SlowView {
+    fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+        let delay = self.delay;
+        let answer = cx.use_resource((), move || async move {
+            tokio::time::sleep(delay).await;
+            Ok::<_, ()>(42)
+        });
+        match answer.value() {
+            Some(answer) => Node::new("p").child(format!("answer: {answer}")),
+            None => Node::new("p").child("loading"),
+        }
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn settled_render_waits_for_resources() {
+    let (session, _) = LiveSession::new(|| SlowView {
+        delay: Duration::from_millis(10),
+    });
+    let html = session.settled_render(Duration::from_secs(1)).await;
+
+    assert!(html.contains("answer: 42"), "{html}");
+}
+
+#[tokio::test(start_paused = true)]
+async fn settled_render_gives_up_after_timeout() {
+    let (session, _) = LiveSession::new(|| SlowView {
+        delay: Duration::from_secs(10),
+    });
+    let html = session.settled_render(Duration::from_millis(50)).await;
+
+    assert!(html.contains("loading"), "{html}");
+}
+
+#[tokio::test(start_paused = true)]
+async fn render_stream_sends_settled_views_after_the_page() {
+    let (session, _) = LiveSession::new(|| SlowView {
+        delay: Duration::from_millis(10),
+    });
+    let chunks: Vec<_> = Arc::new(session)
+        .render_stream(Duration::from_secs(1))
+        .collect()
+        .await;
+
+    let [RenderChunk::Page(page), RenderChunk::View { html, .. }] = &chunks[..] else {
+        panic!("unexpected chunks: {chunks:?}");
+    };
+    assert!(page.contains("loading"), "{page}");
+    assert!(html.contains("answer: 42"), "{html}");
+}
@@ -2,8 +2,9 @@ use std::{sync::Arc, time::Duration};
 
 use blastview::{
     context::ViewContext,
+    html,
     node::Node,
-    use_effect, use_state,
+    use_state,
     view::{View, ViewRef},
 };
 use tracing_subscriber::{
@@ -56,17 +57,18 @@ impl View for CounterView {
     fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
         let (count, set_count) = use_state!(cx, 0);
 
-        Node::new("div")
-            .child(Node::new("button").child(format!("Sub")).on("click", {
-                let set_count = Arc::clone(&set_count);
-                move || set_count(count - 1)
-            }))
-            .child(count.to_string())
-            .child(
-                Node::new("button")
-                    .child(format!("Add"))
-                    .on("click", move || set_count(count + 1)),
-            )
+        html! {
+            <div>
+                <button on:click={{
+                    let set_count = Arc::clone(&set_count);
+                    move || set_count(count - 1)
+                }}>
+                    "Sub"
+                </button>
+                {count}
+                <button on:click={move || set_count(count + 1)}>"Add"</button>
+            </div>
+        }
     }
 }
 
@@ -76,21 +78,7 @@ impl View for AutoIncrementCounterView {
     fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
         let (count, set_count) = cx.use_state(0);
 
-        use_effect!(cx, move || {
-            let set_count = set_count.clone();
-            let task = tokio::spawn(async move {
-                let mut interval = tokio::time::interval(Duration::from_secs(1));
-                interval.tick().await;
-                let mut current_count = 0;
-                loop {
-                    interval.tick().await;
-                    set_count(current_count + 1);
-                    current_count += 1;
-                }
-            });
-
-            move || task.abort()
-        });
+        cx.use_interval(Duration::from_secs(1), move || set_count(count + 1));
 
         Node::new("div").child(format!("Count is: {}", count.to_string()))
     }
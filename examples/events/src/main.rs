@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use blastview::{context::ViewContext, node::Node, view::View};
 use tracing_subscriber::{
     filter::{EnvFilter, LevelFilter},
@@ -29,6 +31,7 @@ impl View for EventView {
             .attr("class", "container")
             .child(click_event_button())
             .child(double_click_event_button())
+            .child(async_click_event_button())
     }
 }
 
@@ -47,3 +50,12 @@ fn double_click_event_button() -> impl Into<Node> {
             println!("a button was double-clicked");
         })
 }
+
+fn async_click_event_button() -> impl Into<Node> {
+    Node::new("button")
+        .child("I handle click events asynchronously")
+        .on_async("click", async || {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            println!("an async click handler has finished");
+        })
+}
@@ -1,4 +1,11 @@
-use blastview::{context::ViewContext, node::Node, use_async_memo, use_state, view::View};
+use std::time::Duration;
+
+use blastview::{
+    context::{ResourceState, ViewContext},
+    node::Node,
+    use_state,
+    view::{Suspense, View},
+};
 use tracing_subscriber::{
     filter::{EnvFilter, LevelFilter},
     fmt,
@@ -18,23 +25,31 @@ async fn main() -> std::io::Result<()> {
         )
         .init();
 
-    blaster::serve(|| MyView).await
+    // the first page is served with its numbers already computed
+    blaster::Server::new(|| MyView)
+        .await_resources(Duration::from_secs(2))
+        .serve()
+        .await
 }
 
 struct MyView;
 
 impl View for MyView {
+    fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+        // both numbers are shown at once, once they have been computed
+        cx.create_view(|| Suspense::new(|| FibView, || Node::new("p").child("computing...").into()))
+    }
+}
+
+struct FibView;
+
+impl View for FibView {
     fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
         let (count, set_count) = use_state!(cx, 38);
-        let (is_loading, fib) = use_async_memo!(
-            cx,
-            async || {
-                // this function may take a lot of time to run
-                // thus, use async memo
-                fib(count)
-            },
-            count
-        );
+        // this function may take a lot of time to run
+        // thus, compute it in a resource
+        let fib = cx.use_resource(count, move || async move { checked_fib(count) });
+        let next_fib = cx.use_resource(count, move || async move { checked_fib(count + 1) });
 
         Node::new("div")
             .attr("class", "container")
@@ -48,14 +63,28 @@ impl View for MyView {
                     .child("Add")
                     .on("click", move || set_count(count + 1)),
             )
-            .child(Node::new("p").child(format!(
-                "fib({count}): {}",
-                if is_loading {
-                    "computing..."
-                } else {
-                    &fib.unwrap().to_string()
-                }
-            )))
+            .child(Node::new("button").child("Recompute").on("click", {
+                let fib = fib.clone();
+                move || fib.refetch()
+            }))
+            .child(Node::new("p").child(match fib.state() {
+                ResourceState::Loading => String::new(),
+                ResourceState::Ready(value) => format!("fib({count}): {value}"),
+                ResourceState::Error(error) => format!("error: {error}"),
+            }))
+            .child(Node::new("p").child(match next_fib.state() {
+                ResourceState::Loading => String::new(),
+                ResourceState::Ready(value) => format!("fib({}): {value}", count + 1),
+                ResourceState::Error(error) => format!("error: {error}"),
+            }))
+    }
+}
+
+fn checked_fib(n: u64) -> Result<u64, String> {
+    if n > 45 {
+        Err(format!("fib({n}) would take too long"))
+    } else {
+        Ok(fib(n))
     }
 }
 
@@ -1,4 +1,9 @@
-use blastview::{context::ViewContext, node::Node, view::View};
+use blastview::{
+    context::ViewContext,
+    html::{Sanitizer, Trusted},
+    node::Node,
+    view::View,
+};
 use tracing_subscriber::{
     filter::{EnvFilter, LevelFilter},
     fmt,
@@ -44,4 +49,12 @@ fn my_component() -> impl Into<Node> {
                 .child("This one's onclick attribute was removed.")
                 .attr("onclick", r#"alert("hii")"#),
         )
+        // HTML we wrote ourselves is rendered as is
+        .child(Trusted::assume_safe(
+            "<p>This paragraph is <strong>trusted</strong> HTML.</p>",
+        ))
+        // HTML from users keeps its formatting, but loses its scripts
+        .child(Sanitizer::default().sanitize(
+            r#"<p onmouseover="alert('hii')">This one was <em>sanitized</em>.<script>alert("hii")</script></p>"#,
+        ))
 }
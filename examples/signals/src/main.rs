@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use blastview::{context::ViewContext, node::Node, use_effect, view::View};
+use tracing_subscriber::{
+    filter::{EnvFilter, LevelFilter},
+    fmt,
+    layer::SubscriberExt,
+    util::SubscriberInitExt,
+};
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    tracing_subscriber::registry()
+        .with(fmt::layer())
+        .with(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::INFO.into())
+                .from_env()
+                .unwrap(),
+        )
+        .init();
+
+    blaster::serve(|| ProgressView).await
+}
+
+struct ProgressView;
+
+impl View for ProgressView {
+    fn render(&self, cx: &impl ViewContext) -> impl Into<Node> {
+        let progress = cx.use_signal(0u32);
+
+        use_effect!(cx, {
+            let progress = progress.clone();
+            move || {
+                // updates only patch the bound nodes, the view itself is rendered once
+                let task = tokio::spawn(async move {
+                    let mut interval = tokio::time::interval(Duration::from_millis(50));
+                    loop {
+                        interval.tick().await;
+                        progress.update(|progress| *progress = (*progress + 1) % 101);
+                    }
+                });
+
+                move || task.abort()
+            }
+        });
+
+        Node::new("div")
+            .attr("class", "container")
+            .child(
+                Node::new("div")
+                    .attr("style", "width: 300px; border: 1px solid black")
+                    .child(
+                        Node::new("div")
+                            .bind(
+                                "style",
+                                progress.map(|progress| {
+                                    format!("width: {progress}%; height: 16px; background: green")
+                                }),
+                            )
+                            .child(""),
+                    ),
+            )
+            .child(Node::new("p").child(progress.map(|progress| format!("{progress}%"))))
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use blastview::{
+    context::ViewContext,
+    node::Node,
+    template::{Bindings, TemplateError, Templates},
+    view::TryView,
+};
+use tracing_subscriber::{
+    filter::{EnvFilter, LevelFilter},
+    fmt,
+    layer::SubscriberExt,
+    util::SubscriberInitExt,
+};
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    tracing_subscriber::registry()
+        .with(fmt::layer())
+        .with(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::INFO.into())
+                .from_env()
+                .unwrap(),
+        )
+        .init();
+
+    let templates = Templates::new(concat!(env!("CARGO_MANIFEST_DIR"), "/templates"));
+    // edit templates/counter.html while the server runs to see every open page update
+    let templates = if cfg!(debug_assertions) {
+        templates.hot_reload(Duration::from_millis(250))
+    } else {
+        templates
+    };
+
+    blaster::serve(move || Counter {
+        templates: templates.clone(),
+    })
+    .await
+}
+
+struct Counter {
+    templates: Templates,
+}
+
+impl TryView for Counter {
+    type Error = TemplateError;
+
+    fn try_render(&self, cx: &impl ViewContext) -> Result<impl Into<Node>, TemplateError> {
+        let template = self.templates.use_template(cx, "counter.html")?;
+        let (count, set_count) = cx.use_state(0);
+        let decrement = set_count.clone();
+
+        Ok(template.render(
+            &Bindings::new()
+                .slot("title", "Template counter")
+                .slot("count", count)
+                .flag("at_zero", count == 0)
+                .on("increment", move || set_count(count + 1))
+                .on("decrement", move || decrement(count - 1)),
+        ))
+    }
+}